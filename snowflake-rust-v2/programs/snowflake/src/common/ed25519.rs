@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+pub fn parse_ed25519_signatures(data: &[u8]) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let count = *data.first().ok_or(ErrorCode::InvalidSignatureInstruction)? as usize;
+    let mut signatures = Vec::with_capacity(count);
+
+    for i in 0..count {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+        let offsets = data
+            .get(start..start + SIGNATURE_OFFSETS_SIZE)
+            .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+        let read = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        // Only trust keys and messages embedded in the verify instruction itself
+        require!(
+            read(2) == CURRENT_INSTRUCTION
+                && read(6) == CURRENT_INSTRUCTION
+                && read(12) == CURRENT_INSTRUCTION,
+            ErrorCode::InvalidSignatureInstruction
+        );
+
+        let pubkey_offset = read(4) as usize;
+        let message_offset = read(8) as usize;
+        let message_size = read(10) as usize;
+        let pubkey = data
+            .get(pubkey_offset..pubkey_offset + PUBKEY_SIZE)
+            .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidSignatureInstruction)?;
+
+        signatures.push((Pubkey::new(pubkey), message.to_vec()));
+    }
+
+    Ok(signatures)
+}
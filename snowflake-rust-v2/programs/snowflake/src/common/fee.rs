@@ -3,11 +3,10 @@ use anchor_lang::solana_program;
 use anchor_lang::solana_program::program::invoke_signed;
 
 use crate::instructions::ExecuteMultisigFlow;
-use crate::state::FLOW_EXECUTION_FEE;
+use crate::state::{Safe, FLOW_EXECUTION_FEE};
 use crate::SAFE_SIGNER_PREFIX;
 
-pub fn charge_fee(ctx: &Context<ExecuteMultisigFlow>) -> Result<()> {
-    let safe = &ctx.accounts.safe;
+pub fn charge_fee(ctx: &Context<ExecuteMultisigFlow>, safe: &Safe) -> Result<()> {
     let safe_signer = &ctx.accounts.safe_signer;
     let caller = &ctx.accounts.caller;
 
@@ -16,7 +15,7 @@ pub fn charge_fee(ctx: &Context<ExecuteMultisigFlow>) -> Result<()> {
         &caller.key,
         FLOW_EXECUTION_FEE,
     );
-    let safe_key = safe.key();
+    let safe_key = ctx.accounts.safe.key();
     let seeds = &[
         SAFE_SIGNER_PREFIX.as_ref(),
         safe_key.as_ref(),
@@ -1,5 +1,9 @@
+pub mod ed25519;
 pub mod fee;
+pub mod realloc;
 pub mod schedule;
 
+pub use ed25519::*;
 pub use fee::*;
+pub use realloc::*;
 pub use schedule::*;
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::program::invoke;
+
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_space: usize,
+) -> Result<()> {
+    let minimum_balance = Rent::get()?.minimum_balance(new_space);
+    let lamports = account.lamports();
+
+    if minimum_balance > lamports {
+        let ix = solana_program::system_instruction::transfer(
+            payer.key,
+            account.key,
+            minimum_balance.checked_sub(lamports).unwrap(),
+        );
+        invoke(
+            &ix,
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    } else if lamports > minimum_balance {
+        let refund = lamports.checked_sub(minimum_balance).unwrap();
+        **account.try_borrow_mut_lamports()? -= refund;
+        **payer.try_borrow_mut_lamports()? += refund;
+    }
+
+    account.realloc(new_space, false)?;
+
+    Ok(())
+}
+
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_space: usize,
+) -> Result<()> {
+    if new_space > account.data_len() {
+        resize_account(account, payer, system_program, new_space)?;
+    }
+
+    Ok(())
+}
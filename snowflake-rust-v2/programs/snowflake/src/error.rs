@@ -85,4 +85,151 @@ pub enum ErrorCode {
 
     #[msg("SnowflakeSafe: Remaining run must be between 0 and 1000")]
     InvalidRemainingRuns,
+
+    #[msg("SnowflakeSafe: Invalid owner weight")]
+    InvalidOwnerWeight,
+
+    #[msg("SnowflakeSafe: Invalid owner roles")]
+    InvalidOwnerRoles,
+
+    #[msg("SnowflakeSafe: Owner does not have the required role")]
+    OwnerRoleRequired,
+
+    #[msg("SnowflakeSafe: Invalid config change")]
+    InvalidConfigChange,
+
+    #[msg("SnowflakeSafe: Config change delay exceeds the maximum")]
+    InvalidConfigChangeDelay,
+
+    #[msg("SnowflakeSafe: Exceed limit pending config changes")]
+    ExceedLimitPendingConfigChanges,
+
+    #[msg("SnowflakeSafe: Config change not found")]
+    ConfigChangeNotFound,
+
+    #[msg("SnowflakeSafe: Config change is not effective yet")]
+    ConfigChangeIsNotEffectiveYet,
+
+    #[msg("SnowflakeSafe: Config change is already effective and can no longer be cancelled")]
+    ConfigChangeIsAlreadyEffective,
+
+    #[msg("SnowflakeSafe: Config change payload does not match the queued change")]
+    ConfigChangePayloadMismatch,
+
+    #[msg("SnowflakeSafe: Safe can grow by at most 10 KiB per instruction")]
+    SafeResizeTooLarge,
+
+    #[msg("SnowflakeSafe: Safe account is too small for the change")]
+    SafeAccountTooSmall,
+
+    #[msg("SnowflakeSafe: Safe vault still holds lamports")]
+    SafeVaultIsNotEmpty,
+
+    #[msg("SnowflakeSafe: Flow is not expired yet")]
+    FlowIsNotExpiredYet,
+
+    #[msg("SnowflakeSafe: Exceed limit allowances")]
+    ExceedLimitAllowances,
+
+    #[msg("SnowflakeSafe: Allowance not found")]
+    AllowanceNotFound,
+
+    #[msg("SnowflakeSafe: Allowance exceeded for the current period")]
+    AllowanceExceeded,
+
+    #[msg("SnowflakeSafe: Allowance source must be owned by the safe signer")]
+    InvalidAllowanceSource,
+
+    #[msg("SnowflakeSafe: Not a guardian.")]
+    InvalidGuardian,
+
+    #[msg("SnowflakeSafe: Invalid guardian threshold")]
+    InvalidGuardianThreshold,
+
+    #[msg("SnowflakeSafe: Recovery not enough approvals")]
+    RecoveryNotEnoughApprovals,
+
+    #[msg("SnowflakeSafe: Recovery is not effective yet")]
+    RecoveryIsNotEffectiveYet,
+
+    #[msg("SnowflakeSafe: Recovery is already effective")]
+    RecoveryIsAlreadyEffective,
+
+    #[msg("SnowflakeSafe: Recovery is expired")]
+    RecoveryIsExpired,
+
+    #[msg("SnowflakeSafe: Recovery is not expired yet")]
+    RecoveryIsNotExpiredYet,
+
+    #[msg("SnowflakeSafe: Max guardian reached.")]
+    InvalidMaxGuardianCount,
+
+    #[msg("SnowflakeSafe: Recovery delay must be greater than 0")]
+    InvalidRecoveryDelay,
+
+    #[msg("SnowflakeSafe: Safe is frozen")]
+    SafeIsFrozen,
+
+    #[msg("SnowflakeSafe: Safe is not frozen")]
+    SafeIsNotFrozen,
+
+    #[msg("SnowflakeSafe: Not the designated pauser of the safe")]
+    InvalidPauser,
+
+    #[msg("SnowflakeSafe: Vault not found")]
+    InvalidVault,
+
+    #[msg("SnowflakeSafe: Max vault reached.")]
+    InvalidMaxVaultCount,
+
+    #[msg("SnowflakeSafe: Safe still has pending or active flows")]
+    SafeHasActiveFlows,
+
+    #[msg("SnowflakeSafe: Account is already migrated")]
+    AccountIsAlreadyMigrated,
+
+    #[msg("SnowflakeSafe: Invalid legacy account")]
+    InvalidLegacyAccount,
+
+    #[msg("SnowflakeSafe: Flow is already on the current owner set")]
+    FlowIsUpToDate,
+
+    #[msg("SnowflakeSafe: Invalid delegate")]
+    InvalidDelegate,
+
+    #[msg("SnowflakeSafe: Delegate expiry must be in the future")]
+    InvalidDelegateExpiry,
+
+    #[msg("SnowflakeSafe: Invalid signature verify instruction")]
+    InvalidSignatureInstruction,
+
+    #[msg("SnowflakeSafe: No approval signatures found")]
+    MissingApprovalSignatures,
+
+    #[msg("SnowflakeSafe: Approval not found")]
+    ApprovalNotFound,
+
+    #[msg("SnowflakeSafe: Approval can only be revoked before execution")]
+    ApprovalCannotBeRevoked,
+
+    #[msg("SnowflakeSafe: Flow content does not match the approved hash")]
+    FlowContentHashMismatch,
+
+    #[msg("SnowflakeSafe: Invalid action index")]
+    InvalidActionIndex,
+
+    #[msg("SnowflakeSafe: Action order must list every action exactly once")]
+    InvalidActionOrder,
+
+    #[msg("SnowflakeSafe: Instruction buffer is already sealed")]
+    InstructionBufferIsSealed,
+
+    #[msg("SnowflakeSafe: Instruction buffer write is out of bounds")]
+    InvalidInstructionBufferWrite,
+
+    #[msg("SnowflakeSafe: Action must reference a sealed instruction buffer of this safe")]
+    InvalidInstructionBuffer,
+
+    #[msg("SnowflakeSafe: Prerequisite flows must be complete before execution")]
+    PrerequisiteFlowNotComplete,
 }
@@ -1,13 +1,14 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::state::{Flow, ProposalStateType, Safe};
+use crate::state::{Flow, OwnerRole, ProposalStateType, Safe};
 
 #[derive(Accounts)]
 pub struct AbortFlow<'info> {
     #[account(mut, has_one = safe @ErrorCode::InvalidSafe)]
     flow: Account<'info, Flow>,
 
+    #[account(mut)]
     safe: Account<'info, Safe>,
 
     requested_by: Signer<'info>,
@@ -15,10 +16,14 @@ pub struct AbortFlow<'info> {
 
 pub fn handler(ctx: Context<AbortFlow>) -> Result<()> {
     let flow = &mut ctx.accounts.flow;
-    let safe = &ctx.accounts.safe;
+    let safe = &mut ctx.accounts.safe;
     let caller = &ctx.accounts.requested_by;
 
     require!(safe.is_owner(&caller.key()), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&caller.key(), OwnerRole::Abort),
+        ErrorCode::OwnerRoleRequired
+    );
 
     require!(
         flow.proposal_stage == ProposalStateType::ExecutionInProgress as u8,
@@ -27,6 +32,7 @@ pub fn handler(ctx: Context<AbortFlow>) -> Result<()> {
 
     let now = Clock::get()?.unix_timestamp;
     flow.proposal_stage = ProposalStateType::Aborted as u8;
+    safe.release_flow();
     flow.last_updated_date = now;
 
     Ok(())
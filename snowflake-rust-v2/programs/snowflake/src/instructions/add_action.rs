@@ -1,27 +1,25 @@
 use anchor_lang::prelude::*;
 
+use crate::common::grow_account;
 use crate::error::ErrorCode;
-use crate::state::{Action, Flow, ProposalStateType};
+use crate::state::{Action, Flow, ProposalStateType, Safe};
 
 #[derive(Accounts)]
 pub struct AddAction<'info> {
-    #[account(mut, has_one = requested_by)]
+    #[account(mut, has_one = requested_by, has_one = safe @ErrorCode::InvalidSafe)]
     flow: Account<'info, Flow>,
 
+    safe: Account<'info, Safe>,
+
+    #[account(mut)]
     requested_by: Signer<'info>,
+
+    system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<AddAction>, client_action: Action, finish_draft: bool) -> Result<()> {
     let flow = &mut ctx.accounts.flow;
-
-    require!(
-        flow.approvals.len() == 0,
-        ErrorCode::FlowMustHaveZeroApproverBeforeUpdate
-    );
-    require!(
-        flow.proposal_stage == ProposalStateType::Draft as u8,
-        ErrorCode::FlowMustBeInDraftedBeforeUpdate
-    );
+    flow.assert_is_editable()?;
 
     let now = Clock::get()?.unix_timestamp;
     flow.actions.push(client_action);
@@ -30,5 +28,10 @@ pub fn handler(ctx: Context<AddAction>, client_action: Action, finish_draft: boo
     }
     flow.last_updated_date = now;
 
-    Ok(())
+    grow_account(
+        &flow.to_account_info(),
+        &ctx.accounts.requested_by.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        flow.space(ctx.accounts.safe.owners.len()),
+    )
 }
@@ -1,33 +1,112 @@
 use crate::error::ErrorCode;
-use crate::state::{ApprovalRecord, Flow, ProposalStateType, Safe};
+use crate::instructions::delegate::is_active_delegate;
+use crate::state::{
+    Action, ApprovalRecord, Flow, OwnerRole, ProposalStateType, Safe, TargetAccountSpec,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
 
 #[derive(Accounts)]
 pub struct ApproveProposal<'info> {
-    #[account(constraint = safe.owner_set_seqno == flow.owner_set_seqno)]
+    #[account(mut, constraint = safe.owner_set_seqno == flow.owner_set_seqno)]
     safe: Account<'info, Safe>,
 
     #[account(mut, has_one = safe @ErrorCode::InvalidSafe)]
     flow: Account<'info, Flow>,
 
-    #[account(mut)]
+    /// Read-only so a parent safe's signer can approve through a flow it executes
     caller: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<ApproveProposal>, is_approved: bool) -> Result<()> {
+pub fn handler(
+    ctx: Context<ApproveProposal>,
+    is_approved: bool,
+    content_hash: [u8; 32],
+    owner: Pubkey,
+) -> Result<()> {
     let flow = &mut ctx.accounts.flow;
-    let safe = &ctx.accounts.safe;
-    let caller = &mut ctx.accounts.caller;
+    let safe = &mut ctx.accounts.safe;
+    let caller = ctx.accounts.caller.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        content_hash == flow.content_hash(),
+        ErrorCode::FlowContentHashMismatch
+    );
+    let delegate = if owner == caller {
+        Pubkey::default()
+    } else {
+        // A delegate passes the owner's delegate record as the first remaining account
+        require!(
+            is_active_delegate(ctx.remaining_accounts, &safe.key(), &owner, &caller, now)?,
+            ErrorCode::InvalidDelegate
+        );
+        caller
+    };
+    record_approval(safe, flow, &owner, &delegate, is_approved, content_hash, now)?;
+    update_flow_after_approval(safe, flow, now);
+
+    Ok(())
+}
+
+/// Builds the action a parent safe's flow runs to approve `flow` of a child safe, where the
+/// parent safe's signer is one of the child safe's owners
+pub fn approve_proposal_action(
+    safe: Pubkey,
+    flow: Pubkey,
+    parent_signer: Pubkey,
+    is_approved: bool,
+    content_hash: [u8; 32],
+) -> Action {
+    let accounts = crate::accounts::ApproveProposal {
+        safe,
+        flow,
+        caller: parent_signer,
+    }
+    .to_account_metas(None);
+    let instruction = crate::instruction::ApproveProposal {
+        is_approved,
+        content_hash,
+        owner: parent_signer,
+    };
+
+    Action {
+        name: "Approve proposal".to_string(),
+        action_code: 0,
+        instruction: instruction.data(),
+        program: crate::ID,
+        accounts: accounts.iter().map(TargetAccountSpec::from).collect(),
+        extra: String::new(),
+        instruction_buffer: None,
+    }
+}
+
+/// `delegate` is the default key when the owner votes themselves
+pub fn record_approval(
+    safe: &Safe,
+    flow: &mut Flow,
+    owner: &Pubkey,
+    delegate: &Pubkey,
+    is_approved: bool,
+    content_hash: [u8; 32],
+    now: i64,
+) -> Result<()> {
+    let owner = *owner;
     let total_owners = safe.owners.len() as u8;
 
-    require!(safe.is_owner(&caller.key()), ErrorCode::InvalidOwner);
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
+    require!(safe.is_owner(&owner), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&owner, OwnerRole::Approve),
+        ErrorCode::OwnerRoleRequired
+    );
     require!(
         flow.approvals.len() < total_owners as usize,
         ErrorCode::ExceedLimitProposalSignatures
     );
 
     require!(
-        flow.is_new_owner_approval(&caller.key()),
+        flow.is_new_owner_approval(&owner),
         ErrorCode::AddressSignedAlready
     );
 
@@ -36,27 +115,23 @@ pub fn handler(ctx: Context<ApproveProposal>, is_approved: bool) -> Result<()> {
         ErrorCode::FlowIsNotReadyYet
     );
 
-    let now = Clock::get()?.unix_timestamp;
     require!(now <= flow.expiry_date, ErrorCode::JobIsExpired);
 
     flow.approvals.push(ApprovalRecord {
         date: now,
         is_approved,
-        owner: *caller.to_account_info().key,
+        owner,
+        delegate: *delegate,
+        content_hash,
     });
 
-    let approvals = flow.get_approvals();
-    let unsigned_owners = total_owners
-        .checked_sub(flow.approvals.len() as u8)
-        .unwrap();
-    if safe.approvals_required.checked_sub(approvals).unwrap() > unsigned_owners {
-        flow.proposal_stage = ProposalStateType::Rejected as u8;
-    }
+    Ok(())
+}
 
-    if approvals == safe.approvals_required {
-        flow.proposal_stage = ProposalStateType::Approved as u8;
+pub fn update_flow_after_approval(safe: &mut Safe, flow: &mut Flow, now: i64) {
+    flow.update_proposal_stage(safe);
+    if flow.proposal_stage == ProposalStateType::Rejected as u8 {
+        safe.release_flow();
     }
     flow.last_updated_date = now;
-
-    Ok(())
 }
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::load_instruction_at_checked;
+
+use crate::common::parse_ed25519_signatures;
+use crate::error::ErrorCode;
+use crate::instructions::approve_proposal::{record_approval, update_flow_after_approval};
+use crate::state::{ApprovalRecord, Flow, Safe};
+
+#[derive(Accounts)]
+pub struct ApproveProposalWithSignatures<'info> {
+    #[account(mut, constraint = safe.owner_set_seqno == flow.owner_set_seqno)]
+    safe: Account<'info, Safe>,
+
+    #[account(mut, has_one = safe @ErrorCode::InvalidSafe)]
+    flow: Account<'info, Flow>,
+
+    /// CHECK: instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    instructions: AccountInfo<'info>,
+}
+
+pub fn handler(ctx: Context<ApproveProposalWithSignatures>) -> Result<()> {
+    let flow = &mut ctx.accounts.flow;
+    let safe = &mut ctx.accounts.safe;
+    let instructions = &ctx.accounts.instructions;
+    let now = Clock::get()?.unix_timestamp;
+
+    let flow_key = flow.key();
+    let content_hash = flow.content_hash();
+    let approve_message = ApprovalRecord::message(
+        &flow_key,
+        flow.owner_set_seqno,
+        flow.approval_nonce,
+        &content_hash,
+        true,
+    );
+    let reject_message = ApprovalRecord::message(
+        &flow_key,
+        flow.owner_set_seqno,
+        flow.approval_nonce,
+        &content_hash,
+        false,
+    );
+
+    let mut recorded = 0;
+    let mut index = 0;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions) {
+        index += 1;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        // The runtime has already verified every signature in this instruction
+        for (signer, message) in parse_ed25519_signatures(&ix.data)? {
+            let is_approved = if message == approve_message {
+                true
+            } else if message == reject_message {
+                false
+            } else {
+                continue;
+            };
+            record_approval(
+                safe,
+                flow,
+                &signer,
+                &Pubkey::default(),
+                is_approved,
+                content_hash,
+                now,
+            )?;
+            recorded += 1;
+        }
+    }
+    require!(recorded > 0, ErrorCode::MissingApprovalSignatures);
+
+    update_flow_after_approval(safe, flow, now);
+
+    Ok(())
+}
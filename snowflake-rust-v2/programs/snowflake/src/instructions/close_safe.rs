@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+
+use crate::error::ErrorCode;
+use crate::state::{Flow, ProposalStateType, Safe, SAFE_SIGNER_PREFIX};
+
+#[derive(Accounts)]
+pub struct CloseSafe<'info> {
+    #[account(mut, close = destination)]
+    safe: Account<'info, Safe>,
+
+    #[account(
+        mut,
+        seeds = [
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe.key().as_ref(),
+        ],
+        bump = safe.signer_bump
+    )]
+    safe_signer: Signer<'info>,
+
+    /// The flow executing this instruction, which still holds its active flow slot
+    #[account(
+        has_one = safe @ErrorCode::InvalidSafe,
+        constraint =
+            flow.proposal_stage == ProposalStateType::Approved as u8
+            || flow.proposal_stage == ProposalStateType::ExecutionInProgress as u8
+    )]
+    flow: Account<'info, Flow>,
+
+    /// CHECK: any account can receive lamports
+    #[account(mut)]
+    destination: AccountInfo<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Every vault in `vault_bumps` must be passed in the remaining accounts. Vaults holding lamports
+/// are swept to the destination with `sweep_signer`, otherwise the safe cannot be closed
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseSafe<'info>>,
+    sweep_signer: bool,
+) -> Result<()> {
+    let safe = &ctx.accounts.safe;
+
+    let other_active_flows = safe
+        .active_flows
+        .saturating_sub(ctx.accounts.flow.is_active() as u32);
+    require!(other_active_flows == 0, ErrorCode::SafeHasActiveFlows);
+
+    let safe_signer = &ctx.accounts.safe_signer;
+    let lamports = safe_signer.lamports();
+    if sweep_signer && lamports > 0 {
+        let ix = solana_program::system_instruction::transfer(
+            safe_signer.key,
+            ctx.accounts.destination.key,
+            lamports,
+        );
+        invoke(
+            &ix,
+            &[
+                safe_signer.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    let safe_key = safe.key();
+    for (index, bump) in safe.vault_bumps.iter().enumerate() {
+        let vault_index = [(index + 1) as u8];
+        let vault_bump = [*bump];
+        let seeds = [
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe_key.as_ref(),
+            &vault_index,
+            &vault_bump,
+        ];
+        let vault_address = Pubkey::create_program_address(&seeds, &crate::ID)
+            .map_err(|_| ErrorCode::InvalidVault)?;
+        let vault = ctx
+            .remaining_accounts
+            .iter()
+            .find(|account| account.key() == vault_address)
+            .ok_or(ErrorCode::InvalidVault)?;
+
+        let lamports = vault.lamports();
+        if lamports == 0 {
+            continue;
+        }
+        require!(sweep_signer, ErrorCode::SafeVaultIsNotEmpty);
+
+        let ix = solana_program::system_instruction::transfer(
+            vault.key,
+            ctx.accounts.destination.key,
+            lamports,
+        );
+        invoke_signed(
+            &ix,
+            &[
+                vault.clone(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds],
+        )?;
+    }
+
+    Ok(())
+}
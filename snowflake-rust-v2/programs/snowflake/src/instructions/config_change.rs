@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::instructions::apply_config_change;
+use crate::state::{ConfigChange, OwnerRole, Safe};
+
+#[derive(Accounts)]
+pub struct ManageConfigChange<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    caller: Signer<'info>,
+}
+
+pub fn apply_handler(ctx: Context<ManageConfigChange>, id: u32, payload: Vec<u8>) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let caller = &ctx.accounts.caller;
+
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
+    require!(safe.is_owner(&caller.key()), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&caller.key(), OwnerRole::Execute),
+        ErrorCode::OwnerRoleRequired
+    );
+
+    let index = safe
+        .find_config_change(id)
+        .ok_or(ErrorCode::ConfigChangeNotFound)?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        safe.pending_config_changes[index].is_effective(now),
+        ErrorCode::ConfigChangeIsNotEffectiveYet
+    );
+
+    let change = safe.pending_config_changes.remove(index);
+    require!(
+        change.payload_hash == ConfigChange::payload_hash(&payload),
+        ErrorCode::ConfigChangePayloadMismatch
+    );
+    apply_config_change(safe, &change, &payload)?;
+
+    require!(
+        safe.to_account_info().data_len() >= Safe::space(safe.max_owners, safe.extra.clone()),
+        ErrorCode::SafeAccountTooSmall
+    );
+    Ok(())
+}
+
+/// Owners with the Abort role can cancel a change on their own while its delay runs, so they
+/// can react to a malicious change that reached the threshold
+pub fn cancel_handler(ctx: Context<ManageConfigChange>, id: u32) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let caller = &ctx.accounts.caller;
+
+    require!(safe.is_owner(&caller.key()), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&caller.key(), OwnerRole::Abort),
+        ErrorCode::OwnerRoleRequired
+    );
+
+    let index = safe
+        .find_config_change(id)
+        .ok_or(ErrorCode::ConfigChangeNotFound)?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        !safe.pending_config_changes[index].is_effective(now),
+        ErrorCode::ConfigChangeIsAlreadyEffective
+    );
+    safe.pending_config_changes.remove(index);
+
+    Ok(())
+}
@@ -1,17 +1,27 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::state::{Flow, ProposalStateType, Safe};
+use crate::state::{Flow, OwnerRole, ProposalStateType, Safe, FLOW_PREFIX};
 
 #[derive(Accounts)]
-#[instruction(account_size : u32)]
+#[instruction(client_flow: Flow)]
 pub struct CreateFlow<'info> {
-    #[account(init, payer = requested_by, space = account_size as usize)]
-    flow: Account<'info, Flow>,
-
     #[account(mut)]
     safe: Account<'info, Safe>,
 
+    #[account(
+        init,
+        payer = requested_by,
+        space = client_flow.space(safe.owners.len()),
+        seeds = [
+            FLOW_PREFIX.as_ref(),
+            safe.key().as_ref(),
+            safe.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    flow: Account<'info, Flow>,
+
     #[account(mut)]
     requested_by: Signer<'info>,
 
@@ -20,7 +30,6 @@ pub struct CreateFlow<'info> {
 
 pub fn handler(
     ctx: Context<CreateFlow>,
-    _account_size: u32,
     client_flow: Flow,
     is_draft: bool,
 ) -> Result<()> {
@@ -30,6 +39,10 @@ pub fn handler(
 
     let safe = &mut ctx.accounts.safe;
     require!(safe.is_owner(&owner.key()), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&owner.key(), OwnerRole::Propose),
+        ErrorCode::OwnerRoleRequired
+    );
     flow.safe = safe.key();
     flow.approvals = Vec::new();
     flow.proposal_stage = if is_draft {
@@ -38,6 +51,8 @@ pub fn handler(
         ProposalStateType::Pending as u8
     };
     flow.owner_set_seqno = safe.owner_set_seqno;
+    flow.proposal_index = safe.proposal_count;
+    safe.proposal_count = safe.proposal_count.checked_add(1).unwrap();
 
     let now = Clock::get()?.unix_timestamp;
     flow.created_date = now;
@@ -45,5 +60,10 @@ pub fn handler(
     flow.apply_flow_data(client_flow, now)?;
 
     require!(flow.validate_flow_data(), ErrorCode::InvalidJobData);
+    require!(
+        safe.vault_bump(flow.vault_index).is_some(),
+        ErrorCode::InvalidVault
+    );
+    safe.active_flows = safe.active_flows.checked_add(1).unwrap();
     Ok(())
 }
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::state::{assert_unique_owners, Safe, SAFE_SIGNER_PREFIX};
+use crate::state::{
+    assert_owner_roles, assert_owner_weights, assert_unique_owners, Safe, ALL_OWNER_ROLES,
+    SAFE_PREFIX, SAFE_SIGNER_PREFIX,
+};
 
 #[derive(Accounts)]
 #[instruction(client_safe: Safe)]
@@ -25,9 +28,53 @@ pub struct CreateSafe<'info> {
     system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(nonce: u64, client_safe: Safe)]
+pub struct CreateSafeWithSeed<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Safe::space(Safe::MAX_OWNERS, client_safe.extra),
+        seeds = [
+            SAFE_PREFIX.as_ref(),
+            payer.key().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    safe: Account<'info, Safe>,
+
+    /// CHECK: must be a valid PDA of safe
+    #[account(
+        seeds = [
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe.key().as_ref()
+        ],
+        bump = client_safe.signer_bump
+    )]
+    pub safe_signer: AccountInfo<'info>,
+
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
 pub fn handler(ctx: Context<CreateSafe>, client_safe: Safe) -> Result<()> {
     let safe = &mut ctx.accounts.safe;
+    initialize_safe(safe, ctx.accounts.payer.key(), client_safe)
+}
 
+pub fn create_with_seed_handler(
+    ctx: Context<CreateSafeWithSeed>,
+    _nonce: u64,
+    client_safe: Safe,
+) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    initialize_safe(safe, ctx.accounts.payer.key(), client_safe)
+}
+
+fn initialize_safe(safe: &mut Safe, creator: Pubkey, mut client_safe: Safe) -> Result<()> {
     require!(
         client_safe.owners.len() > 0usize,
         ErrorCode::InvalidMinOwnerCount
@@ -38,28 +85,68 @@ pub fn handler(ctx: Context<CreateSafe>, client_safe: Safe) -> Result<()> {
         ErrorCode::InvalidMaxOwnerCount
     );
 
+    if client_safe.owner_weights.is_empty() {
+        client_safe.owner_weights = vec![Safe::DEFAULT_OWNER_WEIGHT; client_safe.owners.len()];
+    }
+    assert_owner_weights(&client_safe.owners, &client_safe.owner_weights)?;
+
+    if client_safe.owner_roles.is_empty() {
+        client_safe.owner_roles = vec![ALL_OWNER_ROLES; client_safe.owners.len()];
+    }
+    assert_owner_roles(&client_safe.owners, &client_safe.owner_roles)?;
+    require!(
+        client_safe.can_manage_config(),
+        ErrorCode::InvalidOwnerRoles
+    );
+
     require!(
         client_safe.approvals_required > 0,
         ErrorCode::InvalidMinApprovalsRequired
     );
 
     require!(
-        client_safe.approvals_required <= client_safe.owners.len() as u8,
+        client_safe.approvals_required as u16 <= client_safe.approver_weight(),
         ErrorCode::InvalidMaxApprovalsRequired
     );
 
     assert_unique_owners(&client_safe.owners)?;
 
     require!(
-        client_safe.owners.contains(&ctx.accounts.payer.key()),
+        client_safe.config_change_delay <= Safe::MAX_CONFIG_CHANGE_DELAY,
+        ErrorCode::InvalidConfigChangeDelay
+    );
+
+    require!(
+        client_safe.pauser == Pubkey::default() || client_safe.is_owner(&client_safe.pauser),
+        ErrorCode::InvalidPauser
+    );
+
+    require!(
+        client_safe.owners.contains(&creator),
         ErrorCode::CreatorIsNotAssignedToOwnerList
     );
 
     safe.signer_bump = client_safe.signer_bump;
-    safe.creator = ctx.accounts.payer.key();
+    safe.creator = creator;
     safe.owners = client_safe.owners;
+    safe.owner_weights = client_safe.owner_weights;
+    safe.owner_roles = client_safe.owner_roles;
     safe.approvals_required = client_safe.approvals_required;
     safe.owner_set_seqno = 0;
+    safe.max_owners = Safe::MAX_OWNERS;
+    safe.config_change_delay = client_safe.config_change_delay;
+    safe.config_change_seqno = 0;
+    safe.pending_config_changes = Vec::new();
+    safe.allowances = Vec::new();
+    safe.guardian_threshold = 0;
+    safe.recovery_delay = 0;
+    safe.guardians = Vec::new();
+    safe.frozen = false;
+    safe.pauser = client_safe.pauser;
+    safe.unfreeze_approvals = Vec::new();
+    safe.vault_bumps = Vec::new();
+    safe.active_flows = 0;
+    safe.proposal_count = 0;
     safe.extra = client_safe.extra;
     safe.created_at = Clock::get()?.unix_timestamp;
 
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{Delegate, Safe, DELEGATE_PREFIX};
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    safe: Account<'info, Safe>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = Delegate::SPACE,
+        seeds = [
+            DELEGATE_PREFIX.as_ref(),
+            safe.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    delegate_record: Account<'info, Delegate>,
+
+    #[account(mut)]
+    owner: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    /// CHECK: only used to derive the delegate record, which can be closed after the safe is gone
+    safe: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            DELEGATE_PREFIX.as_ref(),
+            safe.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        close = owner
+    )]
+    delegate_record: Account<'info, Delegate>,
+
+    #[account(mut)]
+    owner: Signer<'info>,
+}
+
+pub fn set_handler(ctx: Context<SetDelegate>, delegate: Pubkey, expires_at: i64) -> Result<()> {
+    let safe = &ctx.accounts.safe;
+    let delegate_record = &mut ctx.accounts.delegate_record;
+    let owner = ctx.accounts.owner.key();
+
+    require!(safe.is_owner(&owner), ErrorCode::InvalidOwner);
+    require!(
+        delegate != owner && delegate != Pubkey::default(),
+        ErrorCode::InvalidDelegate
+    );
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expires_at == 0 || expires_at > now,
+        ErrorCode::InvalidDelegateExpiry
+    );
+
+    delegate_record.safe = safe.key();
+    delegate_record.owner = owner;
+    delegate_record.delegate = delegate;
+    delegate_record.expires_at = expires_at;
+
+    Ok(())
+}
+
+pub fn revoke_handler(_ctx: Context<RevokeDelegate>) -> Result<()> {
+    Ok(())
+}
+
+/// Checks `caller` against the owner's delegate record, passed as the first remaining account
+pub fn is_active_delegate(
+    remaining_accounts: &[AccountInfo],
+    safe: &Pubkey,
+    owner: &Pubkey,
+    caller: &Pubkey,
+    now: i64,
+) -> Result<bool> {
+    // A revoked record is closed, so it is no longer owned by this program
+    let delegate_record = match remaining_accounts.first() {
+        Some(account) if account.owner == &crate::ID && !account.data_is_empty() => account,
+        _ => return Ok(false),
+    };
+    let delegate_record = Account::<Delegate>::try_from(delegate_record)?;
+
+    Ok(delegate_record.is_active_delegate(safe, owner, caller, now))
+}
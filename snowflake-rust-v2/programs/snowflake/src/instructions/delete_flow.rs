@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 
-use crate::state::Flow;
+use crate::error::ErrorCode;
 use crate::state::ProposalStateType;
+use crate::state::{Flow, Safe};
 
 #[derive(Accounts)]
 pub struct DeleteFlow<'info> {
     #[account(
         mut,
         has_one = requested_by,
+        has_one = safe @ErrorCode::InvalidSafe,
         close = requested_by,
         constraint =
             flow.proposal_stage != ProposalStateType::ExecutionInProgress as u8
@@ -16,5 +18,61 @@ pub struct DeleteFlow<'info> {
     )]
     flow: Account<'info, Flow>,
 
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
     requested_by: Signer<'info>,
 }
+
+pub fn handler(ctx: Context<DeleteFlow>) -> Result<()> {
+    if ctx.accounts.flow.is_active() {
+        ctx.accounts.safe.release_flow();
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DeleteExpiredFlow<'info> {
+    #[account(
+        mut,
+        has_one = requested_by,
+        has_one = safe @ErrorCode::InvalidSafe,
+        close = requested_by,
+        constraint =
+            flow.proposal_stage != ProposalStateType::ExecutionInProgress as u8
+            && flow.proposal_stage != ProposalStateType::Complete as u8
+            && flow.proposal_stage != ProposalStateType::Aborted as u8
+    )]
+    flow: Account<'info, Flow>,
+
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    /// CHECK: receives the rent of the flow account
+    #[account(mut)]
+    requested_by: AccountInfo<'info>,
+
+    caller: Signer<'info>,
+}
+
+/// Any owner can clean up a flow that expired before it was executed
+pub fn delete_expired_handler(ctx: Context<DeleteExpiredFlow>) -> Result<()> {
+    let caller = &ctx.accounts.caller;
+    require!(
+        ctx.accounts.safe.is_owner(&caller.key()),
+        ErrorCode::InvalidOwner
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now > ctx.accounts.flow.expiry_date,
+        ErrorCode::FlowIsNotExpiredYet
+    );
+
+    if ctx.accounts.flow.is_active() {
+        ctx.accounts.safe.release_flow();
+    }
+
+    Ok(())
+}
@@ -1,27 +1,24 @@
 use std::collections::HashSet;
+use std::ops::Range;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::program::invoke_signed;
 
 use crate::error::ErrorCode;
-use crate::state::{Flow, Safe, SAFE_SIGNER_PREFIX};
+use crate::state::{Action, Flow, InstructionBuffer, Safe, SAFE_SIGNER_PREFIX};
 
 #[derive(Accounts)]
 pub struct ExecuteMultisigFlow<'info> {
     #[account(mut, has_one=safe @ErrorCode::InvalidSafe)]
     pub flow: Account<'info, Flow>,
 
-    pub safe: Account<'info, Safe>,
+    /// CHECK: loaded with `load_safe`. Anchor would write a typed account back on exit, even
+    /// after a flow action closed the safe, so it is only saved by `release_flow_after_execute`
+    #[account(mut)]
+    pub safe: UncheckedAccount<'info>,
 
-    /// CHECK: sign only
-    #[account(
-        mut,
-        seeds = [
-            SAFE_SIGNER_PREFIX.as_ref(),
-            safe.key().as_ref()
-        ],
-        bump = safe.signer_bump
-    )]
+    /// CHECK: sign only, checked against the safe's signer bump in `load_safe`
+    #[account(mut)]
     pub safe_signer: AccountInfo<'info>,
 
     pub caller: Signer<'info>,
@@ -29,21 +26,42 @@ pub struct ExecuteMultisigFlow<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: &Context<ExecuteMultisigFlow>) -> Result<()> {
-    let safe = &ctx.accounts.safe;
+pub fn handler(
+    ctx: &Context<ExecuteMultisigFlow>,
+    safe: &Safe,
+    actions: Range<usize>,
+) -> Result<()> {
     let flow = &ctx.accounts.flow;
     let caller = &ctx.accounts.caller;
-    let safe_signer = &ctx.accounts.safe_signer.key();
     let execute_by_safe_owner = safe.is_owner(&caller.key());
 
-    for action in flow.clone().actions.iter() {
+    let safe_key = ctx.accounts.safe.key();
+    let vault_index = [flow.vault_index];
+    let vault_bump = [safe
+        .vault_bump(flow.vault_index)
+        .ok_or(ErrorCode::InvalidVault)?];
+    let seeds: Vec<&[u8]> = if flow.vault_index == 0 {
+        vec![SAFE_SIGNER_PREFIX.as_ref(), safe_key.as_ref(), &vault_bump]
+    } else {
+        vec![
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe_key.as_ref(),
+            &vault_index,
+            &vault_bump,
+        ]
+    };
+    let signer = &[&seeds[..]];
+    let vault_signer = Pubkey::create_program_address(&seeds, &crate::ID)
+        .map_err(|_| ErrorCode::InvalidVault)?;
+
+    for action in flow.clone().actions[actions].iter() {
         let mut metas = action.target_account_metas();
         let mut unique_pubkeys: HashSet<Pubkey> = HashSet::new();
 
         for meta in &mut metas {
             unique_pubkeys.insert(meta.pubkey.clone());
 
-            if meta.pubkey.eq(safe_signer) {
+            if meta.pubkey.eq(&vault_signer) {
                 meta.is_signer = true;
             }
 
@@ -58,15 +76,8 @@ pub fn handler(ctx: &Context<ExecuteMultisigFlow>) -> Result<()> {
         let ix = Instruction {
             program_id: action.program,
             accounts: metas,
-            data: action.instruction.clone(),
+            data: instruction_data(ctx, action)?,
         };
-        let safe_key = safe.key();
-        let seeds = &[
-            SAFE_SIGNER_PREFIX.as_ref(),
-            safe_key.as_ref(),
-            &[safe.signer_bump],
-        ];
-        let signer = &[&seeds[..]];
         let account_infos = unique_pubkeys
             .iter()
             .map(|pubkey| -> AccountInfo {
@@ -82,3 +93,53 @@ pub fn handler(ctx: &Context<ExecuteMultisigFlow>) -> Result<()> {
 
     Ok(())
 }
+
+fn instruction_data(ctx: &Context<ExecuteMultisigFlow>, action: &Action) -> Result<Vec<u8>> {
+    let buffer_key = match action.instruction_buffer {
+        Some(buffer_key) => buffer_key,
+        None => return Ok(action.instruction.clone()),
+    };
+    let account = ctx
+        .remaining_accounts
+        .iter()
+        .find(|account| account.key().eq(&buffer_key))
+        .ok_or(ErrorCode::InvalidInstructionBuffer)?;
+    let buffer = Account::<InstructionBuffer>::try_from(account)?;
+    require!(
+        buffer.safe == ctx.accounts.safe.key()
+            && buffer.sealed
+            && buffer.data_hash.as_ref() == action.instruction.as_slice(),
+        ErrorCode::InvalidInstructionBuffer
+    );
+    Ok(buffer.into_inner().data)
+}
+
+pub fn load_safe<'info>(accounts: &ExecuteMultisigFlow<'info>) -> Result<Account<'info, Safe>> {
+    let safe = Account::<Safe>::try_from(&accounts.safe.to_account_info())?;
+    let safe_signer = Pubkey::create_program_address(
+        &[
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe.key().as_ref(),
+            &[safe.signer_bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| anchor_lang::error::ErrorCode::ConstraintSeeds)?;
+    require!(
+        safe_signer == accounts.safe_signer.key(),
+        anchor_lang::error::ErrorCode::ConstraintSeeds
+    );
+    Ok(safe)
+}
+
+/// Actions may update or close the safe through the safe signer, so the safe is loaded again and
+/// only written back while it is still open
+pub fn release_flow_after_execute(accounts: &ExecuteMultisigFlow) -> Result<()> {
+    let safe_info = accounts.safe.to_account_info();
+    if safe_info.lamports() == 0 {
+        return Ok(());
+    }
+    let mut safe = Account::<Safe>::try_from(&safe_info)?;
+    safe.release_flow();
+    safe.exit(&crate::ID)
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::common::grow_account;
+use crate::error::ErrorCode;
+use crate::state::{Action, Flow, Safe};
+
+#[derive(Accounts)]
+pub struct EditAction<'info> {
+    #[account(mut, has_one = requested_by, has_one = safe @ErrorCode::InvalidSafe)]
+    flow: Account<'info, Flow>,
+
+    safe: Account<'info, Safe>,
+
+    #[account(mut)]
+    requested_by: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn replace_handler(ctx: Context<EditAction>, index: u16, client_action: Action) -> Result<()> {
+    let flow = &mut ctx.accounts.flow;
+    flow.assert_is_editable()?;
+
+    let action = flow
+        .actions
+        .get_mut(index as usize)
+        .ok_or(ErrorCode::InvalidActionIndex)?;
+    *action = client_action;
+    flow.last_updated_date = Clock::get()?.unix_timestamp;
+
+    grow_account(
+        &flow.to_account_info(),
+        &ctx.accounts.requested_by.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        flow.space(ctx.accounts.safe.owners.len()),
+    )
+}
+
+pub fn remove_handler(ctx: Context<EditAction>, index: u16) -> Result<()> {
+    let flow = &mut ctx.accounts.flow;
+    flow.assert_is_editable()?;
+
+    require!(
+        (index as usize) < flow.actions.len(),
+        ErrorCode::InvalidActionIndex
+    );
+    flow.actions.remove(index as usize);
+    flow.last_updated_date = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
+
+pub fn reorder_handler(ctx: Context<EditAction>, order: Vec<u16>) -> Result<()> {
+    let flow = &mut ctx.accounts.flow;
+    flow.assert_is_editable()?;
+
+    flow.reorder_actions(&order)?;
+    flow.last_updated_date = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
@@ -1,18 +1,32 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::instructions::{do_execute_multisig_flow, ExecuteMultisigFlow};
-use crate::state::static_config::{ProposalStateType, TriggerType};
+use crate::instructions::{
+    do_execute_multisig_flow, load_safe, release_flow_after_execute, ExecuteMultisigFlow,
+};
+use crate::state::static_config::{OwnerRole, ProposalStateType, TriggerType};
+use crate::state::{Flow, Safe};
 
-pub fn handler(ctx: Context<ExecuteMultisigFlow>) -> Result<()> {
-    validate_multisig_flow_before_execute(&ctx)?;
+pub fn handler(ctx: Context<ExecuteMultisigFlow>, batch_size: u16) -> Result<()> {
+    let safe = load_safe(ctx.accounts)?;
+    validate_multisig_flow_before_execute(&ctx, &safe)?;
 
     let mut result = Ok(());
     let now = Clock::get()?.unix_timestamp;
     if ctx.accounts.flow.trigger_type == TriggerType::Manual as u8 {
-        result = do_execute_multisig_flow::handler(&ctx);
+        let actions = ctx.accounts.flow.next_action_batch(batch_size);
+        let is_last_batch = actions.end == ctx.accounts.flow.actions.len();
+        result = do_execute_multisig_flow::handler(&ctx, &safe, actions.clone());
+        if result.is_ok() && is_last_batch {
+            release_flow_after_execute(ctx.accounts)?;
+        }
         let flow = &mut ctx.accounts.flow;
-        flow.proposal_stage = ProposalStateType::Complete as u8;
+        flow.executed_actions = actions.end as u16;
+        flow.proposal_stage = if is_last_batch {
+            ProposalStateType::Complete as u8
+        } else {
+            ProposalStateType::ExecutionInProgress as u8
+        };
         flow.last_updated_date = now;
     } else {
         let flow = &mut ctx.accounts.flow;
@@ -29,20 +43,58 @@ pub fn handler(ctx: Context<ExecuteMultisigFlow>) -> Result<()> {
     result
 }
 
-pub fn validate_multisig_flow_before_execute(ctx: &Context<ExecuteMultisigFlow>) -> Result<()> {
-    let safe = &ctx.accounts.safe;
+pub fn validate_multisig_flow_before_execute(
+    ctx: &Context<ExecuteMultisigFlow>,
+    safe: &Safe,
+) -> Result<()> {
     let flow = &ctx.accounts.flow;
     let caller = &ctx.accounts.caller;
     let execute_by_safe_owner = safe.is_owner(&caller.key());
 
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
     require!(execute_by_safe_owner, ErrorCode::InvalidOwner);
     require!(
-        flow.proposal_stage == ProposalStateType::Approved as u8,
+        safe.has_role(&caller.key(), OwnerRole::Execute),
+        ErrorCode::OwnerRoleRequired
+    );
+    if flow
+        .actions
+        .iter()
+        .any(|action| action.modifies_account(&ctx.accounts.safe.key()))
+    {
+        require!(
+            safe.has_role(&caller.key(), OwnerRole::ManageConfig),
+            ErrorCode::OwnerRoleRequired
+        );
+    }
+    require!(
+        flow.proposal_stage == ProposalStateType::Approved as u8 || flow.is_execution_resumable(),
         ErrorCode::RequestIsNotApprovedYet
     );
 
     let now = Clock::get()?.unix_timestamp;
-    require!(now <= flow.expiry_date, ErrorCode::JobIsExpired);
+    require!(!flow.is_expired_for_execute(now), ErrorCode::JobIsExpired);
+
+    for prerequisite in flow.prerequisites.iter() {
+        require!(
+            is_prerequisite_complete(ctx, prerequisite)?,
+            ErrorCode::PrerequisiteFlowNotComplete
+        );
+    }
 
     Ok(())
 }
+
+fn is_prerequisite_complete(ctx: &Context<ExecuteMultisigFlow>, key: &Pubkey) -> Result<bool> {
+    let account = match ctx
+        .remaining_accounts
+        .iter()
+        .find(|account| account.key().eq(key))
+    {
+        Some(account) => account,
+        None => return Ok(false),
+    };
+    let prerequisite = Account::<Flow>::try_from(account)?;
+    Ok(prerequisite.safe == ctx.accounts.safe.key()
+        && prerequisite.proposal_stage == ProposalStateType::Complete as u8)
+}
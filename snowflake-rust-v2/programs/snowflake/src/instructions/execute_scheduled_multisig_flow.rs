@@ -2,12 +2,16 @@ use anchor_lang::prelude::*;
 
 use crate::common::charge_fee;
 use crate::error::ErrorCode;
-use crate::instructions::{do_execute_multisig_flow, ExecuteMultisigFlow};
+use crate::instructions::{
+    do_execute_multisig_flow, load_safe, release_flow_after_execute, ExecuteMultisigFlow,
+};
 use crate::state::static_config::ProposalStateType;
+use crate::state::Safe;
 
 pub fn handler<'info>(ctx: Context<ExecuteMultisigFlow>, is_successful_run: bool) -> Result<()> {
-    validate_scheduled_multisig_flow_before_execute(&ctx)?;
-    charge_fee(&ctx)?;
+    let safe = load_safe(ctx.accounts)?;
+    validate_scheduled_multisig_flow_before_execute(&ctx, &safe)?;
+    charge_fee(&ctx, &safe)?;
 
     let now = Clock::get()?.unix_timestamp;
     let flow = &ctx.accounts.flow;
@@ -18,7 +22,7 @@ pub fn handler<'info>(ctx: Context<ExecuteMultisigFlow>, is_successful_run: bool
             flow.is_due_for_execute(now),
             ErrorCode::JobIsNotDueForExecution
         );
-        result = do_execute_multisig_flow::handler(&ctx);
+        result = do_execute_multisig_flow::handler(&ctx, &safe, 0..flow.actions.len());
     } else {
         require!(
             flow.is_schedule_expired(now),
@@ -28,19 +32,24 @@ pub fn handler<'info>(ctx: Context<ExecuteMultisigFlow>, is_successful_run: bool
 
     let flow = &mut ctx.accounts.flow;
     flow.update_after_schedule_run(now, is_successful_run);
+    flow.last_updated_date = now;
     if !flow.has_remaining_runs() {
         flow.proposal_stage = ProposalStateType::Complete as u8;
+        if result.is_ok() {
+            release_flow_after_execute(ctx.accounts)?;
+        }
     }
-    flow.last_updated_date = now;
 
     result
 }
 
 pub fn validate_scheduled_multisig_flow_before_execute(
     ctx: &Context<ExecuteMultisigFlow>,
+    safe: &Safe,
 ) -> Result<()> {
     let flow = &ctx.accounts.flow;
 
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
     require!(
         flow.proposal_stage == ProposalStateType::ExecutionInProgress as u8,
         ErrorCode::RequestIsNotExecutedYet
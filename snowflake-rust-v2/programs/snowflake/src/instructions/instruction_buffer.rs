@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{InstructionBuffer, OwnerRole, Safe};
+
+#[derive(Accounts)]
+#[instruction(capacity: u32)]
+pub struct CreateInstructionBuffer<'info> {
+    #[account(init, payer = creator, space = InstructionBuffer::space(capacity as usize))]
+    buffer: Account<'info, InstructionBuffer>,
+
+    safe: Account<'info, Safe>,
+
+    #[account(mut)]
+    creator: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageInstructionBuffer<'info> {
+    #[account(mut, has_one = creator)]
+    buffer: Account<'info, InstructionBuffer>,
+
+    creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseInstructionBuffer<'info> {
+    #[account(mut, has_one = creator, close = creator)]
+    buffer: Account<'info, InstructionBuffer>,
+
+    #[account(mut)]
+    creator: Signer<'info>,
+}
+
+pub fn create_handler(ctx: Context<CreateInstructionBuffer>, _capacity: u32) -> Result<()> {
+    let safe = &ctx.accounts.safe;
+    let creator = ctx.accounts.creator.key();
+
+    require!(safe.is_owner(&creator), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&creator, OwnerRole::Propose),
+        ErrorCode::OwnerRoleRequired
+    );
+
+    let buffer = &mut ctx.accounts.buffer;
+    buffer.safe = safe.key();
+    buffer.creator = creator;
+
+    Ok(())
+}
+
+pub fn write_handler(
+    ctx: Context<ManageInstructionBuffer>,
+    offset: u32,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let buffer = &mut ctx.accounts.buffer;
+    require!(!buffer.sealed, ErrorCode::InstructionBufferIsSealed);
+    require!(
+        buffer.write(offset as usize, &chunk),
+        ErrorCode::InvalidInstructionBufferWrite
+    );
+    require!(
+        InstructionBuffer::space(buffer.data.len()) <= buffer.to_account_info().data_len(),
+        ErrorCode::InvalidInstructionBufferWrite
+    );
+
+    Ok(())
+}
+
+pub fn seal_handler(ctx: Context<ManageInstructionBuffer>) -> Result<()> {
+    let buffer = &mut ctx.accounts.buffer;
+    require!(!buffer.sealed, ErrorCode::InstructionBufferIsSealed);
+    buffer.seal();
+
+    Ok(())
+}
+
+pub fn close_handler(_ctx: Context<CloseInstructionBuffer>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use std::io::Cursor;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::common::{grow_account, resize_account};
+use crate::error::ErrorCode;
+use crate::state::{Flow, LegacyFlow, LegacySafe, Safe};
+
+#[derive(Accounts)]
+pub struct MigrateSafe<'info> {
+    /// CHECK: legacy layout, deserialized in the handler
+    #[account(mut, owner = crate::ID)]
+    safe: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateFlow<'info> {
+    /// CHECK: legacy layout, deserialized in the handler
+    #[account(mut, owner = crate::ID)]
+    flow: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_safe_handler(ctx: Context<MigrateSafe>) -> Result<()> {
+    let safe_info = ctx.accounts.safe.to_account_info();
+    let legacy_safe: LegacySafe = read_legacy_account::<Safe, LegacySafe>(&safe_info)?;
+    require!(
+        safe_info.data_len() == legacy_safe.space(),
+        ErrorCode::InvalidLegacyAccount
+    );
+
+    require!(
+        legacy_safe.owners.contains(&ctx.accounts.payer.key()),
+        ErrorCode::InvalidOwner
+    );
+
+    let safe = legacy_safe.into_safe();
+    resize_account(
+        &safe_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        Safe::space(safe.max_owners, safe.extra.clone()),
+    )?;
+
+    write_account(&safe_info, &safe)
+}
+
+pub fn migrate_flow_handler(ctx: Context<MigrateFlow>) -> Result<()> {
+    let flow_info = ctx.accounts.flow.to_account_info();
+    let legacy_flow: LegacyFlow = read_legacy_account::<Flow, LegacyFlow>(&flow_info)?;
+    // Legacy flows only grew in place, so every byte after the serialized flow is still zeroed
+    require!(
+        is_zero_padded(&flow_info, 8 + legacy_flow.try_to_vec()?.len())?,
+        ErrorCode::InvalidLegacyAccount
+    );
+    let safe = &mut ctx.accounts.safe;
+
+    require!(legacy_flow.safe == safe.key(), ErrorCode::InvalidSafe);
+    require!(
+        safe.is_owner(&ctx.accounts.payer.key()),
+        ErrorCode::InvalidOwner
+    );
+
+    let flow = legacy_flow.into_flow();
+    if flow.is_active() {
+        safe.active_flows = safe.active_flows.checked_add(1).unwrap();
+    }
+
+    grow_account(
+        &flow_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        flow.space(safe.owners.len()),
+    )?;
+
+    write_account(&flow_info, &flow)
+}
+
+fn read_legacy_account<T, L>(info: &AccountInfo) -> Result<L>
+where
+    T: AccountDeserialize + Discriminator,
+    L: AnchorDeserialize,
+{
+    let data = info.try_borrow_data()?;
+    require!(
+        T::try_deserialize(&mut &data[..]).is_err(),
+        ErrorCode::AccountIsAlreadyMigrated
+    );
+    require!(
+        data.len() >= 8 && data[..8] == T::discriminator(),
+        ErrorCode::InvalidLegacyAccount
+    );
+
+    L::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidLegacyAccount))
+}
+
+fn is_zero_padded(info: &AccountInfo, used: usize) -> Result<bool> {
+    let data = info.try_borrow_data()?;
+    Ok(data[used..].iter().all(|byte| *byte == 0))
+}
+
+fn write_account<T: AccountSerialize>(info: &AccountInfo, account: &T) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    let dst: &mut [u8] = &mut data;
+    let mut cursor = Cursor::new(dst);
+    account.try_serialize(&mut cursor)
+}
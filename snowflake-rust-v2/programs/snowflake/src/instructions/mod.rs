@@ -1,21 +1,49 @@
 pub mod abort_flow;
 pub mod add_action;
 pub mod approve_proposal;
+pub mod approve_proposal_with_signatures;
+pub mod close_safe;
+pub mod config_change;
 pub mod create_flow;
 pub mod create_safe;
+pub mod delegate;
 pub mod delete_flow;
 pub mod do_execute_multisig_flow;
+pub mod edit_action;
 pub mod execute_multisig_flow;
 pub mod execute_scheduled_multisig_flow;
+pub mod instruction_buffer;
+pub mod migrate;
+pub mod pause_safe;
+pub mod rebase_flow;
+pub mod recovery;
+pub mod resize_safe;
+pub mod revoke_approval;
+pub mod spend_allowance;
+pub mod update_flow;
 pub mod update_safe;
 
 pub use abort_flow::*;
 pub use add_action::*;
 pub use approve_proposal::*;
+pub use approve_proposal_with_signatures::*;
+pub use close_safe::*;
+pub use config_change::*;
 pub use create_flow::*;
 pub use create_safe::*;
+pub use delegate::*;
 pub use delete_flow::*;
 pub use do_execute_multisig_flow::*;
+pub use edit_action::*;
 pub use execute_multisig_flow::*;
 pub use execute_scheduled_multisig_flow::*;
+pub use instruction_buffer::*;
+pub use migrate::*;
+pub use pause_safe::*;
+pub use rebase_flow::*;
+pub use recovery::*;
+pub use resize_safe::*;
+pub use revoke_approval::*;
+pub use spend_allowance::*;
+pub use update_flow::*;
 pub use update_safe::*;
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::Safe;
+
+#[derive(Accounts)]
+pub struct PauseSafe<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    caller: Signer<'info>,
+}
+
+pub fn pause_handler(ctx: Context<PauseSafe>) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let caller = &ctx.accounts.caller;
+
+    require!(safe.is_owner(&caller.key()), ErrorCode::InvalidOwner);
+    require!(safe.pauser == caller.key(), ErrorCode::InvalidPauser);
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
+
+    safe.frozen = true;
+    safe.unfreeze_approvals = Vec::new();
+
+    Ok(())
+}
+
+pub fn unpause_handler(ctx: Context<PauseSafe>) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let caller = &ctx.accounts.caller;
+
+    let owner_index = safe
+        .owners
+        .iter()
+        .position(|owner| *owner == caller.key())
+        .ok_or(ErrorCode::InvalidOwner)?;
+    require!(safe.frozen, ErrorCode::SafeIsNotFrozen);
+    require!(
+        !safe.has_unfreeze_approval(owner_index),
+        ErrorCode::AddressSignedAlready
+    );
+
+    safe.add_unfreeze_approval(owner_index);
+
+    if safe.get_unfreeze_weight() >= safe.approvals_required as u16 {
+        safe.frozen = false;
+        safe.unfreeze_approvals = Vec::new();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::common::grow_account;
+use crate::error::ErrorCode;
+use crate::state::{Flow, OwnerRole, ProposalStateType, Safe};
+
+#[derive(Accounts)]
+pub struct RebaseFlow<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    #[account(mut, has_one = safe @ErrorCode::InvalidSafe)]
+    flow: Account<'info, Flow>,
+
+    #[account(mut)]
+    caller: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RebaseFlow>) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let flow = &mut ctx.accounts.flow;
+    let caller = &ctx.accounts.caller;
+
+    require!(safe.is_owner(&caller.key()), ErrorCode::InvalidOwner);
+    require!(
+        safe.has_role(&caller.key(), OwnerRole::Propose),
+        ErrorCode::OwnerRoleRequired
+    );
+    require!(
+        flow.proposal_stage == ProposalStateType::Pending as u8,
+        ErrorCode::FlowIsNotReadyYet
+    );
+    require!(
+        flow.owner_set_seqno != safe.owner_set_seqno,
+        ErrorCode::FlowIsUpToDate
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now <= flow.expiry_date, ErrorCode::JobIsExpired);
+
+    flow.approvals.retain(|approval| safe.has_role(&approval.owner, OwnerRole::Approve));
+    flow.owner_set_seqno = safe.owner_set_seqno;
+    flow.update_proposal_stage(safe);
+    if flow.proposal_stage == ProposalStateType::Rejected as u8 {
+        safe.release_flow();
+    }
+    flow.last_updated_date = now;
+
+    // The new owner set may need more approval slots
+    grow_account(
+        &flow.to_account_info(),
+        &ctx.accounts.caller.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        flow.space(safe.owners.len()),
+    )
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::instructions::{assert_valid_owner_set, replace_owners};
+use crate::state::{OwnerSetChange, Recovery, Safe, RECOVERY_PREFIX};
+
+#[derive(Accounts)]
+#[instruction(new_owners: Vec<Pubkey>)]
+pub struct InitiateRecovery<'info> {
+    safe: Account<'info, Safe>,
+
+    #[account(
+        init,
+        payer = guardian,
+        space = Recovery::space(new_owners.len(), Safe::MAX_GUARDIANS),
+        seeds = [
+            RECOVERY_PREFIX.as_ref(),
+            safe.key().as_ref()
+        ],
+        bump
+    )]
+    recovery: Account<'info, Recovery>,
+
+    #[account(mut)]
+    guardian: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    safe: Account<'info, Safe>,
+
+    #[account(mut, has_one = safe @ErrorCode::InvalidSafe)]
+    recovery: Account<'info, Recovery>,
+
+    guardian: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRecovery<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    #[account(
+        mut,
+        has_one = safe @ErrorCode::InvalidSafe,
+        has_one = initiated_by,
+        close = initiated_by
+    )]
+    recovery: Account<'info, Recovery>,
+
+    /// CHECK: receives the rent of the recovery account
+    #[account(mut)]
+    initiated_by: AccountInfo<'info>,
+
+    caller: Signer<'info>,
+}
+
+pub fn initiate_handler(
+    ctx: Context<InitiateRecovery>,
+    new_owners: Vec<Pubkey>,
+    approvals_required: u8,
+) -> Result<()> {
+    let safe = &ctx.accounts.safe;
+    let recovery = &mut ctx.accounts.recovery;
+    let guardian = &ctx.accounts.guardian;
+
+    require!(safe.is_guardian(&guardian.key()), ErrorCode::InvalidGuardian);
+    let change = OwnerSetChange::new(new_owners.to_vec(), vec![], vec![], approvals_required);
+    assert_valid_owner_set(safe, &change)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    recovery.safe = safe.key();
+    recovery.initiated_by = guardian.key();
+    recovery.initiated_at = now;
+    recovery.effective_at = now.checked_add(safe.recovery_delay as i64).unwrap();
+    // The recovery stays executable for another recovery delay, so a bogus one cannot block
+    // the safe forever
+    recovery.expires_at = recovery
+        .effective_at
+        .checked_add(safe.recovery_delay as i64)
+        .unwrap();
+    recovery.approvals_required = approvals_required;
+    recovery.new_owners = new_owners;
+    recovery.approvals = vec![guardian.key()];
+
+    Ok(())
+}
+
+pub fn approve_handler(ctx: Context<ApproveRecovery>) -> Result<()> {
+    let safe = &ctx.accounts.safe;
+    let recovery = &mut ctx.accounts.recovery;
+    let guardian = &ctx.accounts.guardian;
+
+    require!(safe.is_guardian(&guardian.key()), ErrorCode::InvalidGuardian);
+    let now = Clock::get()?.unix_timestamp;
+    require!(!recovery.is_expired(now), ErrorCode::RecoveryIsExpired);
+    require!(
+        !recovery.approvals.contains(&guardian.key()),
+        ErrorCode::AddressSignedAlready
+    );
+
+    // Approvals from removed guardians are dropped so the list stays within MAX_GUARDIANS.
+    recovery
+        .approvals
+        .retain(|approval| safe.guardians.contains(approval));
+    recovery.approvals.push(guardian.key());
+
+    Ok(())
+}
+
+pub fn execute_handler(ctx: Context<CloseRecovery>) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let recovery = &ctx.accounts.recovery;
+    let caller = &ctx.accounts.caller;
+
+    require!(safe.is_guardian(&caller.key()), ErrorCode::InvalidGuardian);
+    require!(
+        safe.guardian_threshold > 0
+            && recovery.get_guardian_approvals(&safe.guardians) >= safe.guardian_threshold,
+        ErrorCode::RecoveryNotEnoughApprovals
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= recovery.effective_at,
+        ErrorCode::RecoveryIsNotEffectiveYet
+    );
+    require!(!recovery.is_expired(now), ErrorCode::RecoveryIsExpired);
+
+    // Recovered owners start with the default weights and roles
+    let change = OwnerSetChange::new(
+        recovery.new_owners.to_vec(),
+        vec![],
+        vec![],
+        recovery.approvals_required,
+    );
+    replace_owners(safe, change)?;
+    safe.pending_config_changes = Vec::new();
+
+    Ok(())
+}
+
+pub fn cancel_recovery_handler(ctx: Context<CloseRecovery>) -> Result<()> {
+    let safe = &ctx.accounts.safe;
+    let recovery = &ctx.accounts.recovery;
+    let caller = ctx.accounts.caller.key();
+
+    let now = Clock::get()?.unix_timestamp;
+    if safe.is_guardian(&caller) && !safe.is_owner(&caller) {
+        // Guardians clear stale recoveries, which matters when the owners lost their keys
+        require!(recovery.is_expired(now), ErrorCode::RecoveryIsNotExpiredYet);
+    } else {
+        require!(safe.is_owner(&caller), ErrorCode::InvalidOwner);
+        require!(
+            now < recovery.effective_at || recovery.is_expired(now),
+            ErrorCode::RecoveryIsAlreadyEffective
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+use crate::common::resize_account;
+use crate::error::ErrorCode;
+use crate::instructions::update_safe::queue_config_change;
+use crate::state::{ConfigChangeType, Safe, SAFE_SIGNER_PREFIX};
+
+#[derive(Accounts)]
+pub struct ResizeSafe<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    #[account(
+        seeds = [
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe.key().as_ref(),
+        ],
+        bump = safe.signer_bump
+    )]
+    safe_signer: Signer<'info>,
+
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn set_max_owners_handler(ctx: Context<ResizeSafe>, max_owners: u8) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+
+    if safe.config_change_delay > 0 {
+        assert_valid_max_owners(safe, max_owners)?;
+        queue_config_change(
+            safe,
+            ConfigChangeType::SetMaxOwners,
+            Pubkey::default(),
+            max_owners.into(),
+            &[],
+        )?;
+        // The account grows now so the change can be applied without a payer later.
+        let new_space = Safe::space(max_owners, safe.extra.clone());
+        return grow(ctx, new_space);
+    }
+
+    change_max_owners(safe, max_owners)?;
+    let new_space = Safe::space(safe.max_owners, safe.extra.clone());
+    resize(ctx, new_space)
+}
+
+pub fn set_extra_handler(ctx: Context<ResizeSafe>, extra: String) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+
+    if safe.config_change_delay > 0 {
+        let new_space = Safe::space(safe.max_owners, extra.clone());
+        queue_config_change(
+            safe,
+            ConfigChangeType::SetExtra,
+            Pubkey::default(),
+            0,
+            &extra.try_to_vec()?,
+        )?;
+        return grow(ctx, new_space);
+    }
+
+    change_extra(safe, extra)?;
+    let new_space = Safe::space(safe.max_owners, safe.extra.clone());
+    resize(ctx, new_space)
+}
+
+pub fn change_max_owners(safe: &mut Safe, max_owners: u8) -> Result<()> {
+    assert_valid_max_owners(safe, max_owners)?;
+    safe.max_owners = max_owners;
+    Ok(())
+}
+
+pub fn change_extra(safe: &mut Safe, extra: String) -> Result<()> {
+    safe.extra = extra;
+    Ok(())
+}
+
+fn assert_valid_max_owners(safe: &Safe, max_owners: u8) -> Result<()> {
+    require!(
+        max_owners <= Safe::MAX_OWNERS_LIMIT,
+        ErrorCode::InvalidMaxOwnerCount
+    );
+    require!(
+        safe.owners.len() < max_owners.into(),
+        ErrorCode::InvalidMaxOwnerCount
+    );
+    Ok(())
+}
+
+fn grow(ctx: Context<ResizeSafe>, new_space: usize) -> Result<()> {
+    if new_space <= ctx.accounts.safe.to_account_info().data_len() {
+        return Ok(());
+    }
+    resize(ctx, new_space)
+}
+
+fn resize(ctx: Context<ResizeSafe>, new_space: usize) -> Result<()> {
+    let safe = ctx.accounts.safe.to_account_info();
+
+    require!(
+        new_space.saturating_sub(safe.data_len()) <= MAX_PERMITTED_DATA_INCREASE,
+        ErrorCode::SafeResizeTooLarge
+    );
+
+    resize_account(
+        &safe,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        new_space,
+    )
+}
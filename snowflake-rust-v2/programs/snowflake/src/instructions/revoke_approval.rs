@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::instructions::approve_proposal::update_flow_after_approval;
+use crate::instructions::delegate::is_active_delegate;
+use crate::state::{Flow, ProposalStateType, Safe};
+
+#[derive(Accounts)]
+pub struct RevokeApproval<'info> {
+    #[account(mut, constraint = safe.owner_set_seqno == flow.owner_set_seqno)]
+    safe: Account<'info, Safe>,
+
+    #[account(mut, has_one = safe @ErrorCode::InvalidSafe)]
+    flow: Account<'info, Flow>,
+
+    caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RevokeApproval>) -> Result<()> {
+    let flow = &mut ctx.accounts.flow;
+    let safe = &mut ctx.accounts.safe;
+    let caller = ctx.accounts.caller.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
+    require!(
+        flow.proposal_stage == ProposalStateType::Pending as u8
+            || flow.proposal_stage == ProposalStateType::Approved as u8,
+        ErrorCode::ApprovalCannotBeRevoked
+    );
+
+    let index = flow
+        .find_approval(&caller)
+        .ok_or(ErrorCode::ApprovalNotFound)?;
+    let owner = flow.approvals[index].owner;
+    if owner != caller {
+        // A delegate passes the owner's delegate record as the first remaining account
+        require!(
+            is_active_delegate(ctx.remaining_accounts, &safe.key(), &owner, &caller, now)?,
+            ErrorCode::InvalidDelegate
+        );
+    }
+    flow.approvals.remove(index);
+    // Signatures collected before the revoke can no longer be submitted
+    flow.approval_nonce = flow.approval_nonce.checked_add(1).unwrap();
+
+    flow.proposal_stage = ProposalStateType::Pending as u8;
+    update_flow_after_approval(safe, flow, now);
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::error::ErrorCode;
+use crate::state::{Safe, NATIVE_SOL_MINT, SAFE_SIGNER_PREFIX};
+
+#[derive(Accounts)]
+pub struct SpendSolAllowance<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    /// CHECK: sign only
+    #[account(
+        mut,
+        seeds = [
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe.key().as_ref()
+        ],
+        bump = safe.signer_bump
+    )]
+    safe_signer: AccountInfo<'info>,
+
+    /// CHECK: any account can receive lamports
+    #[account(mut)]
+    recipient: AccountInfo<'info>,
+
+    spender: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SpendTokenAllowance<'info> {
+    #[account(mut)]
+    safe: Account<'info, Safe>,
+
+    /// CHECK: sign only
+    #[account(
+        seeds = [
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe.key().as_ref()
+        ],
+        bump = safe.signer_bump
+    )]
+    safe_signer: AccountInfo<'info>,
+
+    #[account(mut, constraint = source.owner == safe_signer.key() @ErrorCode::InvalidAllowanceSource)]
+    source: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    destination: Account<'info, TokenAccount>,
+
+    spender: Signer<'info>,
+
+    token_program: Program<'info, Token>,
+}
+
+pub fn sol_handler(ctx: Context<SpendSolAllowance>, amount: u64) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let safe_signer = &ctx.accounts.safe_signer;
+    let recipient = &ctx.accounts.recipient;
+
+    debit_allowance(safe, &ctx.accounts.spender.key(), &NATIVE_SOL_MINT, amount)?;
+
+    let ix =
+        solana_program::system_instruction::transfer(safe_signer.key, recipient.key, amount);
+    let safe_key = safe.key();
+    let seeds = &[
+        SAFE_SIGNER_PREFIX.as_ref(),
+        safe_key.as_ref(),
+        &[safe.signer_bump],
+    ];
+    let signer = &[&seeds[..]];
+    invoke_signed(
+        &ix,
+        &[safe_signer.to_account_info(), recipient.to_account_info()],
+        signer,
+    )?;
+
+    Ok(())
+}
+
+pub fn token_handler(ctx: Context<SpendTokenAllowance>, amount: u64) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let mint = ctx.accounts.source.mint;
+
+    debit_allowance(safe, &ctx.accounts.spender.key(), &mint, amount)?;
+
+    let safe_key = safe.key();
+    let seeds = &[
+        SAFE_SIGNER_PREFIX.as_ref(),
+        safe_key.as_ref(),
+        &[safe.signer_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.source.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.safe_signer.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+fn debit_allowance(safe: &mut Safe, spender: &Pubkey, mint: &Pubkey, amount: u64) -> Result<()> {
+    require!(!safe.frozen, ErrorCode::SafeIsFrozen);
+
+    let index = safe
+        .find_allowance(spender, mint)
+        .ok_or(ErrorCode::AllowanceNotFound)?;
+    let now = Clock::get()?.unix_timestamp;
+    safe.allowances[index].spend(amount, now)
+}
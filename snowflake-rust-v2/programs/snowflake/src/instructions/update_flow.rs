@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::common::grow_account;
+use crate::error::ErrorCode;
+use crate::state::{Flow, Safe};
+
+#[derive(Accounts)]
+pub struct UpdateFlow<'info> {
+    #[account(mut, has_one = requested_by, has_one = safe @ErrorCode::InvalidSafe)]
+    flow: Account<'info, Flow>,
+
+    safe: Account<'info, Safe>,
+
+    #[account(mut)]
+    requested_by: Signer<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateFlow>, mut client_flow: Flow) -> Result<()> {
+    let flow = &mut ctx.accounts.flow;
+    let safe = &ctx.accounts.safe;
+    flow.assert_is_editable()?;
+
+    // Actions are edited through the dedicated action instructions
+    client_flow.actions = std::mem::take(&mut flow.actions);
+
+    let now = Clock::get()?.unix_timestamp;
+    flow.apply_flow_data(client_flow, now)?;
+    require!(flow.validate_flow_data(), ErrorCode::InvalidJobData);
+    require!(
+        safe.vault_bump(flow.vault_index).is_some(),
+        ErrorCode::InvalidVault
+    );
+    flow.last_updated_date = now;
+
+    grow_account(
+        &flow.to_account_info(),
+        &ctx.accounts.requested_by.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        flow.space(safe.owners.len()),
+    )
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::state::Safe;
+use crate::instructions::resize_safe::{change_extra, change_max_owners};
+use crate::state::{
+    assert_owner_roles, assert_owner_weights, assert_unique_owners, Allowance, AllowanceChange,
+    ConfigChange, ConfigChangeType, GuardiansChange, OwnerSetChange, Safe, ALL_OWNER_ROLES,
+};
 use crate::SAFE_SIGNER_PREFIX;
 
 #[derive(Accounts)]
@@ -19,55 +23,365 @@ pub struct AuthSafe<'info> {
     safe_signer: Signer<'info>,
 }
 
-pub fn add_owner_handler(ctx: Context<AuthSafe>, owner: Pubkey) -> Result<()> {
+pub fn add_owner_handler(ctx: Context<AuthSafe>, owner: Pubkey, weight: u8) -> Result<()> {
     let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        return queue_config_change(safe, ConfigChangeType::AddOwner, owner, weight as u64, &[]);
+    }
+    add_owner(safe, owner, weight)
+}
+
+pub fn remove_owner_handler(ctx: Context<AuthSafe>, owner: Pubkey) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        return queue_config_change(safe, ConfigChangeType::RemoveOwner, owner, 0, &[]);
+    }
+    remove_owner(safe, owner)
+}
+
+pub fn change_threshold_handler(ctx: Context<AuthSafe>, approvals_required: u8) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        return queue_config_change(
+            safe,
+            ConfigChangeType::ChangeThreshold,
+            Pubkey::default(),
+            approvals_required as u64,
+            &[],
+        );
+    }
+    change_threshold(safe, approvals_required)
+}
+
+pub fn set_owner_roles_handler(ctx: Context<AuthSafe>, owner: Pubkey, roles: u8) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        return queue_config_change(
+            safe,
+            ConfigChangeType::SetOwnerRoles,
+            owner,
+            roles as u64,
+            &[],
+        );
+    }
+    set_owner_roles(safe, owner, roles)
+}
+
+pub fn set_config_change_delay_handler(ctx: Context<AuthSafe>, delay: u32) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        return queue_config_change(
+            safe,
+            ConfigChangeType::SetConfigChangeDelay,
+            Pubkey::default(),
+            delay as u64,
+            &[],
+        );
+    }
+    set_config_change_delay(safe, delay)
+}
+
+pub fn set_owners_handler(
+    ctx: Context<AuthSafe>,
+    owners: Vec<Pubkey>,
+    owner_weights: Vec<u8>,
+    owner_roles: Vec<u8>,
+    approvals_required: u8,
+) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let change = OwnerSetChange::new(owners, owner_weights, owner_roles, approvals_required);
+    if safe.config_change_delay > 0 {
+        assert_valid_owner_set(safe, &change)?;
+        return queue_config_change(
+            safe,
+            ConfigChangeType::SetOwners,
+            Pubkey::default(),
+            approvals_required.into(),
+            &change.try_to_vec()?,
+        );
+    }
+    replace_owners(safe, change)
+}
+
+pub fn swap_owner_handler(
+    ctx: Context<AuthSafe>,
+    old_owner: Pubkey,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        find_swapped_owner(safe, &old_owner, &new_owner)?;
+        return queue_config_change(
+            safe,
+            ConfigChangeType::SwapOwner,
+            old_owner,
+            0,
+            &new_owner.try_to_vec()?,
+        );
+    }
+    swap_owner(safe, old_owner, new_owner)
+}
+
+pub fn set_allowance_handler(
+    ctx: Context<AuthSafe>,
+    spender: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+    period: u32,
+) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let change = AllowanceChange {
+        spender,
+        mint,
+        amount,
+        period,
+    };
+    if safe.config_change_delay > 0 {
+        return queue_config_change(
+            safe,
+            ConfigChangeType::SetAllowance,
+            spender,
+            amount,
+            &change.try_to_vec()?,
+        );
+    }
+    set_allowance(safe, change)
+}
+
+pub fn set_guardians_handler(
+    ctx: Context<AuthSafe>,
+    guardians: Vec<Pubkey>,
+    guardian_threshold: u8,
+    recovery_delay: u32,
+) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    let change = GuardiansChange {
+        guardians,
+        guardian_threshold,
+        recovery_delay,
+    };
+    if safe.config_change_delay > 0 {
+        assert_valid_guardians(&change)?;
+        return queue_config_change(
+            safe,
+            ConfigChangeType::SetGuardians,
+            Pubkey::default(),
+            guardian_threshold.into(),
+            &change.try_to_vec()?,
+        );
+    }
+    set_guardians(safe, change)
+}
+
+pub fn set_pauser_handler(ctx: Context<AuthSafe>, pauser: Pubkey) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+    if safe.config_change_delay > 0 {
+        assert_valid_pauser(safe, &pauser)?;
+        return queue_config_change(safe, ConfigChangeType::SetPauser, pauser, 0, &[]);
+    }
+    set_pauser(safe, pauser)
+}
+
+pub fn add_vault_handler(ctx: Context<AuthSafe>) -> Result<()> {
+    let safe = &mut ctx.accounts.safe;
+
+    require!(
+        safe.vault_bumps.len() < Safe::MAX_VAULTS.into(),
+        ErrorCode::InvalidMaxVaultCount
+    );
+
+    let vault_index = safe.vault_bumps.len() as u8 + 1;
+    let safe_key = safe.key();
+    let (_, bump) = Pubkey::find_program_address(
+        &[
+            SAFE_SIGNER_PREFIX.as_ref(),
+            safe_key.as_ref(),
+            &[vault_index],
+        ],
+        ctx.program_id,
+    );
+    safe.vault_bumps.push(bump);
+
+    Ok(())
+}
+
+pub fn replace_owners(safe: &mut Safe, change: OwnerSetChange) -> Result<()> {
+    assert_valid_owner_set(safe, &change)?;
+
+    safe.owners = change.owners;
+    safe.owner_weights = change.owner_weights;
+    safe.owner_roles = change.owner_roles;
+    safe.approvals_required = change.approvals_required;
+    safe.unfreeze_approvals = Vec::new();
+    if !safe.is_owner(&safe.pauser) {
+        safe.pauser = Pubkey::default();
+    }
+    safe.owner_set_seqno = safe.owner_set_seqno.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+pub fn assert_valid_owner_set(safe: &Safe, change: &OwnerSetChange) -> Result<()> {
+    require!(!change.owners.is_empty(), ErrorCode::InvalidMinOwnerCount);
+    require!(
+        change.owners.len() < safe.max_owners.into(),
+        ErrorCode::InvalidMaxOwnerCount
+    );
+    assert_unique_owners(&change.owners)?;
+    assert_owner_weights(&change.owners, &change.owner_weights)?;
+    assert_owner_roles(&change.owners, &change.owner_roles)?;
+
+    let new_safe = Safe {
+        owners: change.owners.clone(),
+        owner_weights: change.owner_weights.clone(),
+        owner_roles: change.owner_roles.clone(),
+        ..Safe::default()
+    };
+    require!(new_safe.can_manage_config(), ErrorCode::InvalidOwnerRoles);
+
+    require!(
+        change.approvals_required > 0,
+        ErrorCode::InvalidMinApprovalsRequired
+    );
+    require!(
+        change.approvals_required as u16 <= new_safe.approver_weight(),
+        ErrorCode::InvalidMaxApprovalsRequired
+    );
+
+    Ok(())
+}
+
+pub fn apply_config_change(safe: &mut Safe, change: &ConfigChange, payload: &[u8]) -> Result<()> {
+    let change_type = change.change_type;
+    if change_type == ConfigChangeType::AddOwner as u8 {
+        add_owner(safe, change.owner, change.value as u8)
+    } else if change_type == ConfigChangeType::RemoveOwner as u8 {
+        remove_owner(safe, change.owner)
+    } else if change_type == ConfigChangeType::ChangeThreshold as u8 {
+        change_threshold(safe, change.value as u8)
+    } else if change_type == ConfigChangeType::SetOwnerRoles as u8 {
+        set_owner_roles(safe, change.owner, change.value as u8)
+    } else if change_type == ConfigChangeType::SetConfigChangeDelay as u8 {
+        set_config_change_delay(safe, change.value as u32)
+    } else if change_type == ConfigChangeType::SetAllowance as u8 {
+        set_allowance(safe, read_payload(payload)?)
+    } else if change_type == ConfigChangeType::SetMaxOwners as u8 {
+        change_max_owners(safe, change.value as u8)
+    } else if change_type == ConfigChangeType::SetExtra as u8 {
+        change_extra(safe, read_payload(payload)?)
+    } else if change_type == ConfigChangeType::SetGuardians as u8 {
+        set_guardians(safe, read_payload(payload)?)
+    } else if change_type == ConfigChangeType::SetPauser as u8 {
+        set_pauser(safe, change.owner)
+    } else if change_type == ConfigChangeType::SetOwners as u8 {
+        replace_owners(safe, read_payload(payload)?)
+    } else if change_type == ConfigChangeType::SwapOwner as u8 {
+        swap_owner(safe, change.owner, read_payload(payload)?)
+    } else {
+        err!(ErrorCode::InvalidConfigChange)
+    }
+}
+
+pub(crate) fn queue_config_change(
+    safe: &mut Safe,
+    change_type: ConfigChangeType,
+    owner: Pubkey,
+    value: u64,
+    payload: &[u8],
+) -> Result<()> {
+    require!(
+        safe.pending_config_changes.len() < Safe::MAX_PENDING_CONFIG_CHANGES.into(),
+        ErrorCode::ExceedLimitPendingConfigChanges
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    safe.pending_config_changes.push(ConfigChange {
+        id: safe.config_change_seqno,
+        change_type: change_type as u8,
+        owner,
+        value,
+        queued_at: now,
+        effective_at: now.checked_add(safe.config_change_delay as i64).unwrap(),
+        payload_hash: ConfigChange::payload_hash(payload),
+    });
+    safe.config_change_seqno = safe.config_change_seqno.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+fn read_payload<T: AnchorDeserialize>(payload: &[u8]) -> Result<T> {
+    T::try_from_slice(payload).map_err(|_| error!(ErrorCode::ConfigChangePayloadMismatch))
+}
+
+fn add_owner(safe: &mut Safe, owner: Pubkey, weight: u8) -> Result<()> {
+    safe.assert_owner_data()?;
     let mut safe_owners = safe.owners.to_vec();
 
     require!(
         !safe_owners.contains(&owner),
         ErrorCode::DuplicateOwnerInSafe
     );
+    require!(weight > 0, ErrorCode::InvalidOwnerWeight);
 
     safe_owners.push(owner);
 
     require!(
-        safe_owners.len() < Safe::MAX_OWNERS.into(),
+        safe_owners.len() < safe.max_owners.into(),
         ErrorCode::InvalidMaxOwnerCount
     );
 
     safe.owners = safe_owners;
+    safe.owner_weights.push(weight);
+    safe.owner_roles.push(ALL_OWNER_ROLES);
     safe.owner_set_seqno = safe.owner_set_seqno.checked_add(1).unwrap();
 
     Ok(())
 }
 
-pub fn remove_owner_handler(ctx: Context<AuthSafe>, owner: Pubkey) -> Result<()> {
-    let safe = &mut ctx.accounts.safe;
+fn remove_owner(safe: &mut Safe, owner: Pubkey) -> Result<()> {
+    safe.assert_owner_data()?;
     let mut safe_owners = safe.owners.to_vec();
-    safe_owners.retain(|safe_owner| *safe_owner != owner);
+    let mut safe_owner_weights = safe.owner_weights.to_vec();
+    let mut safe_owner_roles = safe.owner_roles.to_vec();
+    if let Some(index) = safe_owners
+        .iter()
+        .position(|safe_owner| *safe_owner == owner)
+    {
+        safe_owners.remove(index);
+        safe_owner_weights.remove(index);
+        safe_owner_roles.remove(index);
+        safe.remove_unfreeze_approval(index);
+    }
 
     require!(!safe_owners.contains(&owner), ErrorCode::OwnerIsNotRemoved);
     require!(safe_owners.len() > 0usize, ErrorCode::InvalidMinOwnerCount);
 
-    if (safe_owners.len() as u8) < safe.approvals_required {
-        safe.approvals_required = safe_owners.len() as u8;
+    safe.owners = safe_owners;
+    safe.owner_weights = safe_owner_weights;
+    safe.owner_roles = safe_owner_roles;
+    if safe.pauser == owner {
+        safe.pauser = Pubkey::default();
     }
 
-    safe.owners = safe_owners;
+    require!(safe.can_manage_config(), ErrorCode::InvalidOwnerRoles);
+
+    let approver_weight = safe.approver_weight();
+    require!(approver_weight > 0, ErrorCode::InvalidMinApprovalsRequired);
+    if approver_weight < safe.approvals_required as u16 {
+        safe.approvals_required = approver_weight as u8;
+    }
     safe.owner_set_seqno = safe.owner_set_seqno.checked_add(1).unwrap();
 
     Ok(())
 }
 
-pub fn change_threshold_handler(ctx: Context<AuthSafe>, approvals_required: u8) -> Result<()> {
-    let safe = &mut ctx.accounts.safe;
-
+fn change_threshold(safe: &mut Safe, approvals_required: u8) -> Result<()> {
     require!(
         approvals_required > 0,
         ErrorCode::InvalidMinApprovalsRequired
     );
     require!(
-        approvals_required <= safe.owners.len() as u8,
+        approvals_required as u16 <= safe.approver_weight(),
         ErrorCode::InvalidMaxApprovalsRequired
     );
 
@@ -76,3 +390,142 @@ pub fn change_threshold_handler(ctx: Context<AuthSafe>, approvals_required: u8)
 
     Ok(())
 }
+
+fn set_owner_roles(safe: &mut Safe, owner: Pubkey, roles: u8) -> Result<()> {
+    safe.assert_owner_data()?;
+    let index = safe
+        .owners
+        .iter()
+        .position(|safe_owner| *safe_owner == owner)
+        .ok_or(ErrorCode::InvalidOwner)?;
+    require!(roles & !ALL_OWNER_ROLES == 0, ErrorCode::InvalidOwnerRoles);
+
+    safe.owner_roles[index] = roles;
+
+    require!(safe.can_manage_config(), ErrorCode::InvalidOwnerRoles);
+    require!(
+        safe.approvals_required as u16 <= safe.approver_weight(),
+        ErrorCode::InvalidMaxApprovalsRequired
+    );
+
+    safe.owner_set_seqno = safe.owner_set_seqno.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+fn set_config_change_delay(safe: &mut Safe, delay: u32) -> Result<()> {
+    require!(
+        delay <= Safe::MAX_CONFIG_CHANGE_DELAY,
+        ErrorCode::InvalidConfigChangeDelay
+    );
+
+    safe.config_change_delay = delay;
+
+    Ok(())
+}
+
+fn set_allowance(safe: &mut Safe, change: AllowanceChange) -> Result<()> {
+    match safe.find_allowance(&change.spender, &change.mint) {
+        Some(index) if change.amount == 0 => {
+            safe.allowances.remove(index);
+        }
+        Some(index) => {
+            let allowance = &mut safe.allowances[index];
+            allowance.amount = change.amount;
+            allowance.period = change.period;
+        }
+        None => {
+            require!(change.amount > 0, ErrorCode::AllowanceNotFound);
+            require!(
+                safe.allowances.len() < Safe::MAX_ALLOWANCES.into(),
+                ErrorCode::ExceedLimitAllowances
+            );
+
+            safe.allowances.push(Allowance {
+                spender: change.spender,
+                mint: change.mint,
+                amount: change.amount,
+                spent: 0,
+                period: change.period,
+                period_start: Clock::get()?.unix_timestamp,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn set_guardians(safe: &mut Safe, change: GuardiansChange) -> Result<()> {
+    assert_valid_guardians(&change)?;
+
+    safe.guardians = change.guardians;
+    safe.guardian_threshold = change.guardian_threshold;
+    safe.recovery_delay = change.recovery_delay;
+
+    Ok(())
+}
+
+fn assert_valid_guardians(change: &GuardiansChange) -> Result<()> {
+    require!(
+        change.guardians.len() <= Safe::MAX_GUARDIANS.into(),
+        ErrorCode::InvalidMaxGuardianCount
+    );
+    assert_unique_owners(&change.guardians)?;
+
+    if change.guardians.is_empty() {
+        require!(
+            change.guardian_threshold == 0,
+            ErrorCode::InvalidGuardianThreshold
+        );
+    } else {
+        require!(
+            change.guardian_threshold > 0
+                && change.guardian_threshold as usize <= change.guardians.len(),
+            ErrorCode::InvalidGuardianThreshold
+        );
+        require!(change.recovery_delay > 0, ErrorCode::InvalidRecoveryDelay);
+    }
+
+    Ok(())
+}
+
+fn set_pauser(safe: &mut Safe, pauser: Pubkey) -> Result<()> {
+    assert_valid_pauser(safe, &pauser)?;
+    safe.pauser = pauser;
+
+    Ok(())
+}
+
+fn assert_valid_pauser(safe: &Safe, pauser: &Pubkey) -> Result<()> {
+    require!(
+        *pauser == Pubkey::default() || safe.is_owner(pauser),
+        ErrorCode::InvalidPauser
+    );
+    Ok(())
+}
+
+fn swap_owner(safe: &mut Safe, old_owner: Pubkey, new_owner: Pubkey) -> Result<()> {
+    let index = find_swapped_owner(safe, &old_owner, &new_owner)?;
+
+    safe.owners[index] = new_owner;
+    safe.clear_unfreeze_approval(index);
+    if safe.pauser == old_owner {
+        safe.pauser = Pubkey::default();
+    }
+    safe.owner_set_seqno = safe.owner_set_seqno.checked_add(1).unwrap();
+
+    Ok(())
+}
+
+fn find_swapped_owner(safe: &Safe, old_owner: &Pubkey, new_owner: &Pubkey) -> Result<usize> {
+    let index = safe
+        .owners
+        .iter()
+        .position(|safe_owner| safe_owner == old_owner)
+        .ok_or(ErrorCode::InvalidOwner)?;
+    require!(
+        !safe.owners.contains(new_owner),
+        ErrorCode::DuplicateOwnerInSafe
+    );
+    Ok(index)
+}
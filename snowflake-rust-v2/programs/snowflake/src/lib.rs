@@ -16,15 +16,18 @@ pub mod snowflake {
 
     pub fn create_flow(
         ctx: Context<CreateFlow>,
-        account_size: u32,
         client_flow: Flow,
         is_draft: bool,
     ) -> Result<()> {
-        instructions::create_flow::handler(ctx, account_size, client_flow, is_draft)
+        instructions::create_flow::handler(ctx, client_flow, is_draft)
     }
 
-    pub fn delete_flow(_ctx: Context<DeleteFlow>) -> Result<()> {
-        Ok(())
+    pub fn delete_flow(ctx: Context<DeleteFlow>) -> Result<()> {
+        instructions::delete_flow::handler(ctx)
+    }
+
+    pub fn delete_expired_flow(ctx: Context<DeleteExpiredFlow>) -> Result<()> {
+        instructions::delete_flow::delete_expired_handler(ctx)
     }
 
     pub fn abort_flow(ctx: Context<AbortFlow>) -> Result<()> {
@@ -35,8 +38,16 @@ pub mod snowflake {
         instructions::create_safe::handler(ctx, client_safe)
     }
 
-    pub fn add_owner(ctx: Context<AuthSafe>, owner: Pubkey) -> Result<()> {
-        instructions::update_safe::add_owner_handler(ctx, owner)
+    pub fn create_safe_with_seed(
+        ctx: Context<CreateSafeWithSeed>,
+        nonce: u64,
+        client_safe: Safe,
+    ) -> Result<()> {
+        instructions::create_safe::create_with_seed_handler(ctx, nonce, client_safe)
+    }
+
+    pub fn add_owner(ctx: Context<AuthSafe>, owner: Pubkey, weight: u8) -> Result<()> {
+        instructions::update_safe::add_owner_handler(ctx, owner, weight)
     }
 
     pub fn remove_owner(ctx: Context<AuthSafe>, owner: Pubkey) -> Result<()> {
@@ -47,12 +58,197 @@ pub mod snowflake {
         instructions::update_safe::change_threshold_handler(ctx, threshold)
     }
 
-    pub fn approve_proposal(ctx: Context<ApproveProposal>, is_approved: bool) -> Result<()> {
-        instructions::approve_proposal::handler(ctx, is_approved)
+    pub fn set_owners(
+        ctx: Context<AuthSafe>,
+        owners: Vec<Pubkey>,
+        owner_weights: Vec<u8>,
+        owner_roles: Vec<u8>,
+        approvals_required: u8,
+    ) -> Result<()> {
+        instructions::update_safe::set_owners_handler(
+            ctx,
+            owners,
+            owner_weights,
+            owner_roles,
+            approvals_required,
+        )
+    }
+
+    pub fn swap_owner(ctx: Context<AuthSafe>, old_owner: Pubkey, new_owner: Pubkey) -> Result<()> {
+        instructions::update_safe::swap_owner_handler(ctx, old_owner, new_owner)
+    }
+
+    pub fn set_owner_roles(ctx: Context<AuthSafe>, owner: Pubkey, roles: u8) -> Result<()> {
+        instructions::update_safe::set_owner_roles_handler(ctx, owner, roles)
+    }
+
+    pub fn set_config_change_delay(ctx: Context<AuthSafe>, delay: u32) -> Result<()> {
+        instructions::update_safe::set_config_change_delay_handler(ctx, delay)
+    }
+
+    pub fn apply_config_change(
+        ctx: Context<ManageConfigChange>,
+        id: u32,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        instructions::config_change::apply_handler(ctx, id, payload)
+    }
+
+    pub fn cancel_config_change(ctx: Context<ManageConfigChange>, id: u32) -> Result<()> {
+        instructions::config_change::cancel_handler(ctx, id)
+    }
+
+    pub fn set_max_owners(ctx: Context<ResizeSafe>, max_owners: u8) -> Result<()> {
+        instructions::resize_safe::set_max_owners_handler(ctx, max_owners)
+    }
+
+    pub fn set_extra(ctx: Context<ResizeSafe>, extra: String) -> Result<()> {
+        instructions::resize_safe::set_extra_handler(ctx, extra)
+    }
+
+    pub fn set_allowance(
+        ctx: Context<AuthSafe>,
+        spender: Pubkey,
+        mint: Pubkey,
+        amount: u64,
+        period: u32,
+    ) -> Result<()> {
+        instructions::update_safe::set_allowance_handler(ctx, spender, mint, amount, period)
+    }
+
+    pub fn spend_sol_allowance(ctx: Context<SpendSolAllowance>, amount: u64) -> Result<()> {
+        instructions::spend_allowance::sol_handler(ctx, amount)
+    }
+
+    pub fn spend_token_allowance(ctx: Context<SpendTokenAllowance>, amount: u64) -> Result<()> {
+        instructions::spend_allowance::token_handler(ctx, amount)
+    }
+
+    pub fn set_guardians(
+        ctx: Context<AuthSafe>,
+        guardians: Vec<Pubkey>,
+        guardian_threshold: u8,
+        recovery_delay: u32,
+    ) -> Result<()> {
+        instructions::update_safe::set_guardians_handler(
+            ctx,
+            guardians,
+            guardian_threshold,
+            recovery_delay,
+        )
+    }
+
+    pub fn initiate_recovery(
+        ctx: Context<InitiateRecovery>,
+        new_owners: Vec<Pubkey>,
+        approvals_required: u8,
+    ) -> Result<()> {
+        instructions::recovery::initiate_handler(ctx, new_owners, approvals_required)
+    }
+
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        instructions::recovery::approve_handler(ctx)
+    }
+
+    pub fn execute_recovery(ctx: Context<CloseRecovery>) -> Result<()> {
+        instructions::recovery::execute_handler(ctx)
+    }
+
+    pub fn cancel_recovery(ctx: Context<CloseRecovery>) -> Result<()> {
+        instructions::recovery::cancel_recovery_handler(ctx)
+    }
+
+    pub fn close_safe<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseSafe<'info>>,
+        sweep_signer: bool,
+    ) -> Result<()> {
+        instructions::close_safe::handler(ctx, sweep_signer)
+    }
+
+    pub fn add_vault(ctx: Context<AuthSafe>) -> Result<()> {
+        instructions::update_safe::add_vault_handler(ctx)
     }
 
-    pub fn execute_multisig_flow(ctx: Context<ExecuteMultisigFlow>) -> Result<()> {
-        instructions::execute_multisig_flow::handler(ctx)
+    pub fn set_pauser(ctx: Context<AuthSafe>, pauser: Pubkey) -> Result<()> {
+        instructions::update_safe::set_pauser_handler(ctx, pauser)
+    }
+
+    pub fn pause_safe(ctx: Context<PauseSafe>) -> Result<()> {
+        instructions::pause_safe::pause_handler(ctx)
+    }
+
+    pub fn unpause_safe(ctx: Context<PauseSafe>) -> Result<()> {
+        instructions::pause_safe::unpause_handler(ctx)
+    }
+
+    pub fn migrate_safe(ctx: Context<MigrateSafe>) -> Result<()> {
+        instructions::migrate::migrate_safe_handler(ctx)
+    }
+
+    pub fn migrate_flow(ctx: Context<MigrateFlow>) -> Result<()> {
+        instructions::migrate::migrate_flow_handler(ctx)
+    }
+
+    pub fn rebase_flow(ctx: Context<RebaseFlow>) -> Result<()> {
+        instructions::rebase_flow::handler(ctx)
+    }
+
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        delegate: Pubkey,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::delegate::set_handler(ctx, delegate, expires_at)
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        instructions::delegate::revoke_handler(ctx)
+    }
+
+    pub fn create_instruction_buffer(
+        ctx: Context<CreateInstructionBuffer>,
+        capacity: u32,
+    ) -> Result<()> {
+        instructions::instruction_buffer::create_handler(ctx, capacity)
+    }
+
+    pub fn write_instruction_buffer(
+        ctx: Context<ManageInstructionBuffer>,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        instructions::instruction_buffer::write_handler(ctx, offset, chunk)
+    }
+
+    pub fn seal_instruction_buffer(ctx: Context<ManageInstructionBuffer>) -> Result<()> {
+        instructions::instruction_buffer::seal_handler(ctx)
+    }
+
+    pub fn close_instruction_buffer(ctx: Context<CloseInstructionBuffer>) -> Result<()> {
+        instructions::instruction_buffer::close_handler(ctx)
+    }
+
+    pub fn approve_proposal(
+        ctx: Context<ApproveProposal>,
+        is_approved: bool,
+        content_hash: [u8; 32],
+        owner: Pubkey,
+    ) -> Result<()> {
+        instructions::approve_proposal::handler(ctx, is_approved, content_hash, owner)
+    }
+
+    pub fn revoke_approval(ctx: Context<RevokeApproval>) -> Result<()> {
+        instructions::revoke_approval::handler(ctx)
+    }
+
+    pub fn approve_proposal_with_signatures(
+        ctx: Context<ApproveProposalWithSignatures>,
+    ) -> Result<()> {
+        instructions::approve_proposal_with_signatures::handler(ctx)
+    }
+
+    pub fn execute_multisig_flow(ctx: Context<ExecuteMultisigFlow>, batch_size: u16) -> Result<()> {
+        instructions::execute_multisig_flow::handler(ctx, batch_size)
     }
 
     pub fn execute_scheduled_multisig_flow<'info>(ctx: Context<ExecuteMultisigFlow>) -> Result<()> {
@@ -70,4 +266,24 @@ pub mod snowflake {
     ) -> Result<()> {
         instructions::add_action::handler(ctx, client_action, finish_draft)
     }
+
+    pub fn update_flow(ctx: Context<UpdateFlow>, client_flow: Flow) -> Result<()> {
+        instructions::update_flow::handler(ctx, client_flow)
+    }
+
+    pub fn replace_action(
+        ctx: Context<EditAction>,
+        index: u16,
+        client_action: Action,
+    ) -> Result<()> {
+        instructions::edit_action::replace_handler(ctx, index, client_action)
+    }
+
+    pub fn remove_action(ctx: Context<EditAction>, index: u16) -> Result<()> {
+        instructions::edit_action::remove_handler(ctx, index)
+    }
+
+    pub fn reorder_actions(ctx: Context<EditAction>, order: Vec<u16>) -> Result<()> {
+        instructions::edit_action::reorder_handler(ctx, order)
+    }
 }
@@ -10,9 +10,28 @@ pub struct Action {
     pub program: Pubkey,
     pub accounts: Vec<TargetAccountSpec>,
     pub extra: String,
+    /// When set, `instruction` holds the hash of the sealed buffer data to execute
+    pub instruction_buffer: Option<Pubkey>,
 }
 
 impl Action {
+    pub fn space(&self) -> usize {
+        4 + self.name.len() // name
+        + 4  // action_code
+        + 4 + self.instruction.len() // instruction
+        + 32 // program
+        + 4 + TargetAccountSpec::SPACE * self.accounts.len() // accounts
+        + 4 + self.extra.len() // extra
+        + 1 + self.instruction_buffer.map_or(0, |_| 32) // instruction_buffer
+    }
+
+    /// Every config instruction, reached directly or through a CPI, needs the safe writable
+    pub fn modifies_account(&self, account: &Pubkey) -> bool {
+        self.accounts
+            .iter()
+            .any(|item| item.pubkey == *account && (item.is_writable || item.is_signer))
+    }
+
     pub fn target_account_metas(&self) -> Vec<AccountMeta> {
         self.accounts
             .iter()
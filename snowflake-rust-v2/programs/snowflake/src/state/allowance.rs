@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct Allowance {
+    pub spender: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub spent: u64,
+    pub period: u32,
+    pub period_start: i64,
+}
+
+impl Allowance {
+    pub const SPACE: usize = 32 // spender
+        + 32 // mint
+        + 8  // amount
+        + 8  // spent
+        + 4  // period
+        + 8; // period_start
+
+    pub fn reset_if_period_elapsed(&mut self, now: i64) {
+        if self.period == 0 {
+            return;
+        }
+
+        let elapsed = now.checked_sub(self.period_start).unwrap();
+        if elapsed >= self.period as i64 {
+            self.period_start = now.checked_sub(elapsed % self.period as i64).unwrap();
+            self.spent = 0;
+        }
+    }
+
+    pub fn spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.reset_if_period_elapsed(now);
+
+        let spent = self
+            .spent
+            .checked_add(amount)
+            .ok_or(ErrorCode::AllowanceExceeded)?;
+        require!(spent <= self.amount, ErrorCode::AllowanceExceeded);
+        self.spent = spent;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spend_within_period() {
+        let mut allowance = sample_allowance();
+        let now = 1652946372;
+
+        assert!(allowance.spend(60, now).is_ok());
+        assert!(allowance.spend(40, now + 10).is_ok());
+        assert_eq!(allowance.spent, 100);
+
+        assert!(allowance.spend(1, now + 20).is_err());
+        assert_eq!(allowance.spent, 100);
+    }
+
+    #[test]
+    fn test_spend_resets_after_period() {
+        let mut allowance = sample_allowance();
+        let now = 1652946372;
+
+        assert!(allowance.spend(100, now).is_ok());
+        assert!(allowance.spend(30, now + 86400 * 2 + 5).is_ok());
+        assert_eq!(allowance.spent, 30);
+        assert_eq!(allowance.period_start, now + 86400 * 2);
+    }
+
+    #[test]
+    fn test_spend_without_period() {
+        let mut allowance = sample_allowance();
+        allowance.period = 0;
+        let now = 1652946372;
+
+        assert!(allowance.spend(100, now).is_ok());
+        assert!(allowance.spend(1, now + 86400 * 30).is_err());
+    }
+
+    #[test]
+    fn test_spend_overflow() {
+        let mut allowance = sample_allowance();
+        let now = 1652946372;
+
+        assert!(allowance.spend(60, now).is_ok());
+        assert!(allowance.spend(u64::MAX, now).is_err());
+        assert_eq!(allowance.spent, 60);
+    }
+
+    fn sample_allowance() -> Allowance {
+        Allowance {
+            spender: Pubkey::new_unique(),
+            mint: Pubkey::default(),
+            amount: 100,
+            spent: 0,
+            period: 86400,
+            period_start: 1652946372,
+        }
+    }
+}
@@ -5,4 +5,34 @@ pub struct ApprovalRecord {
     pub owner: Pubkey,
     pub date: i64,
     pub is_approved: bool,
+    pub delegate: Pubkey,
+    pub content_hash: [u8; 32],
+}
+
+pub const APPROVAL_MESSAGE_PREFIX: &[u8] = b"snowflake-safe-approval";
+
+impl ApprovalRecord {
+    pub const SPACE: usize = 32 // owner
+        + 8  // date
+        + 1  // is_approved
+        + 32 // delegate
+        + 32; // content_hash
+
+    /// Message an owner signs off-chain to approve or reject a flow. `approval_nonce` changes on
+    /// every revoke, so a revoked vote cannot be replayed
+    pub fn message(
+        flow: &Pubkey,
+        owner_set_seqno: u64,
+        approval_nonce: u32,
+        content_hash: &[u8; 32],
+        is_approved: bool,
+    ) -> Vec<u8> {
+        let mut message = APPROVAL_MESSAGE_PREFIX.to_vec();
+        message.extend_from_slice(flow.as_ref());
+        message.extend_from_slice(&owner_set_seqno.to_le_bytes());
+        message.extend_from_slice(&approval_nonce.to_le_bytes());
+        message.extend_from_slice(content_hash);
+        message.push(is_approved as u8);
+        message
+    }
 }
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::state::{Safe, ALL_OWNER_ROLES};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct ConfigChange {
+    pub id: u32,
+    pub change_type: u8,
+    pub owner: Pubkey,
+    pub value: u64,
+    pub queued_at: i64,
+    pub effective_at: i64,
+    /// Hash of the arguments that do not fit in `owner` and `value`, resubmitted on apply
+    pub payload_hash: [u8; 32],
+}
+
+impl ConfigChange {
+    pub const SPACE: usize = 4 // id
+        + 1  // change_type
+        + 32 // owner
+        + 8  // value
+        + 8  // queued_at
+        + 8  // effective_at
+        + 32; // payload_hash
+
+    pub fn is_effective(&self, now: i64) -> bool {
+        now >= self.effective_at
+    }
+
+    pub fn payload_hash(payload: &[u8]) -> [u8; 32] {
+        hash(payload).to_bytes()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct AllowanceChange {
+    pub spender: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub period: u32,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct OwnerSetChange {
+    pub owners: Vec<Pubkey>,
+    pub owner_weights: Vec<u8>,
+    pub owner_roles: Vec<u8>,
+    pub approvals_required: u8,
+}
+
+impl OwnerSetChange {
+    /// Owners without explicit weights or roles get the defaults used when creating a safe
+    pub fn new(
+        owners: Vec<Pubkey>,
+        mut owner_weights: Vec<u8>,
+        mut owner_roles: Vec<u8>,
+        approvals_required: u8,
+    ) -> Self {
+        if owner_weights.is_empty() {
+            owner_weights = vec![Safe::DEFAULT_OWNER_WEIGHT; owners.len()];
+        }
+        if owner_roles.is_empty() {
+            owner_roles = vec![ALL_OWNER_ROLES; owners.len()];
+        }
+        OwnerSetChange {
+            owners,
+            owner_weights,
+            owner_roles,
+            approvals_required,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct GuardiansChange {
+    pub guardians: Vec<Pubkey>,
+    pub guardian_threshold: u8,
+    pub recovery_delay: u32,
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Debug)]
+pub struct Delegate {
+    pub safe: Pubkey,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub expires_at: i64,
+}
+
+impl Delegate {
+    pub const SPACE: usize = 8 // Anchor account discriminator
+        + 32 // safe
+        + 32 // owner
+        + 32 // delegate
+        + 8; // expires_at
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+
+    pub fn is_active_delegate(
+        &self,
+        safe: &Pubkey,
+        owner: &Pubkey,
+        delegate: &Pubkey,
+        now: i64,
+    ) -> bool {
+        self.safe == *safe
+            && self.owner == *owner
+            && self.delegate == *delegate
+            && self.is_active(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_active() {
+        let mut delegate = Delegate {
+            safe: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            delegate: Pubkey::new_unique(),
+            expires_at: 0,
+        };
+        assert!(delegate.is_active(1652946372));
+
+        delegate.expires_at = 1652946400;
+        assert!(delegate.is_active(1652946372));
+        assert!(!delegate.is_active(1652946400));
+    }
+
+    #[test]
+    fn test_is_active_delegate() {
+        let safe = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let hot_key = Pubkey::new_unique();
+        let delegate = Delegate {
+            safe,
+            owner,
+            delegate: hot_key,
+            expires_at: 1652946400,
+        };
+
+        assert!(delegate.is_active_delegate(&safe, &owner, &hot_key, 1652946372));
+        assert!(!delegate.is_active_delegate(&safe, &owner, &hot_key, 1652946400));
+        assert!(!delegate.is_active_delegate(&safe, &hot_key, &owner, 1652946372));
+        let other_safe = Pubkey::new_unique();
+        assert!(!delegate.is_active_delegate(&other_safe, &owner, &hot_key, 1652946372));
+    }
+}
@@ -1,11 +1,14 @@
+use std::ops::Range;
+
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
 
 use crate::common::calculate_next_execution_time;
 use crate::error::ErrorCode;
 use crate::state::{
-    Action, ApprovalRecord, TriggerType, DEFAULT_FLOW_EXPIRY_DURATION, DEFAULT_RETRY_WINDOW,
-    MAXIMUM_REMAINING_RUNS_FOR_PROGRAM_TRIGGER, RECURRING_FOREVER, TIMED_FLOW_COMPLETE,
-    TIMED_FLOW_ERROR,
+    Action, ApprovalRecord, ProposalStateType, Safe, TriggerType, DEFAULT_FLOW_EXPIRY_DURATION,
+    DEFAULT_RETRY_WINDOW, MAXIMUM_REMAINING_RUNS_FOR_PROGRAM_TRIGGER, MAX_FLOW_PREREQUISITES,
+    RECURRING_FOREVER, TIMED_FLOW_COMPLETE, TIMED_FLOW_ERROR,
 };
 use snow_util::scheduler::is_valid_utc_offset;
 
@@ -35,16 +38,61 @@ pub struct Flow {
     pub custom_fee: u32,
     pub custom_field_1: i32,
     pub custom_field_2: i32,
-    pub owner_set_seqno: u8,
+    pub owner_set_seqno: u64,
+    pub vault_index: u8,
+    pub proposal_index: u64,
+    pub executed_actions: u16,
+    pub approval_nonce: u32,
     pub external_id: String,
     pub cron: String,
     pub name: String,
     pub extra: String,
+    pub prerequisites: Vec<Pubkey>,
     pub actions: Vec<Action>,
     pub approvals: Vec<ApprovalRecord>,
 }
 
 impl Flow {
+    /// Room for the current content plus one approval per owner
+    pub fn space(&self, total_owners: usize) -> usize {
+        8    // Anchor account discriminator
+        + 32 // requested_by
+        + 32 // safe
+        + 8  // last_updated_date
+        + 1  // proposal_stage
+        + 8  // created_date
+        + 1  // trigger_type
+        + 8  // next_execution_time
+        + 4  // retry_window
+        + 1  // recurring
+        + 2  // remaining_runs
+        + 8  // schedule_end_date
+        + 4  // client_app_id
+        + 8  // last_rent_charged
+        + 8  // last_scheduled_execution
+        + 8  // expiry_date
+        + 1  // expire_on_complete
+        + 32 // app_id
+        + 1  // pay_fee_from
+        + 4  // user_utc_offset
+        + 4  // custom_compute_budget
+        + 4  // custom_fee
+        + 4  // custom_field_1
+        + 4  // custom_field_2
+        + 8  // owner_set_seqno
+        + 1  // vault_index
+        + 8  // proposal_index
+        + 2  // executed_actions
+        + 4  // approval_nonce
+        + 4 + self.external_id.len() // external_id
+        + 4 + self.cron.len() // cron
+        + 4 + self.name.len() // name
+        + 4 + self.extra.len() // extra
+        + 4 + 32 * self.prerequisites.len() // prerequisites
+        + 4 + self.actions.iter().map(|action| action.space()).sum::<usize>() // actions
+        + 4 + ApprovalRecord::SPACE * total_owners // approvals
+    }
+
     pub fn apply_flow_data(&mut self, client_flow: Flow, now: i64) -> Result<()> {
         require!(
             is_valid_utc_offset(client_flow.user_utc_offset),
@@ -79,6 +127,8 @@ impl Flow {
         };
         self.expire_on_complete = false;
         self.extra = client_flow.extra;
+        self.prerequisites = client_flow.prerequisites;
+        self.vault_index = client_flow.vault_index;
 
         if self.trigger_type == TriggerType::Time as u8 {
             if self.retry_window < 1 {
@@ -109,6 +159,57 @@ impl Flow {
         Ok(())
     }
 
+    /// Hash of everything that decides what and when the flow executes
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut data = self.actions.try_to_vec().unwrap();
+        data.push(self.trigger_type);
+        data.extend_from_slice(&self.next_execution_time.to_le_bytes());
+        data.extend_from_slice(&self.retry_window.to_le_bytes());
+        data.push(self.recurring as u8);
+        data.extend_from_slice(&self.remaining_runs.to_le_bytes());
+        data.extend_from_slice(&self.schedule_end_date.to_le_bytes());
+        data.extend_from_slice(&self.user_utc_offset.to_le_bytes());
+        data.push(self.vault_index);
+        data.extend_from_slice(&self.cron.try_to_vec().unwrap());
+        data.extend_from_slice(&self.prerequisites.try_to_vec().unwrap());
+        hash(&data).to_bytes()
+    }
+
+    pub fn assert_is_editable(&self) -> Result<()> {
+        require!(
+            self.approvals.is_empty(),
+            ErrorCode::FlowMustHaveZeroApproverBeforeUpdate
+        );
+        require!(
+            self.proposal_stage == ProposalStateType::Draft as u8,
+            ErrorCode::FlowMustBeInDraftedBeforeUpdate
+        );
+        Ok(())
+    }
+
+    /// `order` lists the current action indexes in their new order
+    pub fn reorder_actions(&mut self, order: &[u16]) -> Result<()> {
+        require!(
+            order.len() == self.actions.len(),
+            ErrorCode::InvalidActionOrder
+        );
+        let mut seen = vec![false; order.len()];
+        for index in order {
+            let index = *index as usize;
+            require!(
+                index < seen.len() && !seen[index],
+                ErrorCode::InvalidActionOrder
+            );
+            seen[index] = true;
+        }
+
+        self.actions = order
+            .iter()
+            .map(|index| self.actions[*index as usize].clone())
+            .collect();
+        Ok(())
+    }
+
     pub fn get_approvals(&self) -> u8 {
         self.approvals
             .iter()
@@ -116,12 +217,44 @@ impl Flow {
             .count() as u8
     }
 
+    pub fn is_active(&self) -> bool {
+        self.proposal_stage == ProposalStateType::Draft as u8
+            || self.proposal_stage == ProposalStateType::Pending as u8
+            || self.proposal_stage == ProposalStateType::Approved as u8
+            || self.proposal_stage == ProposalStateType::ExecutionInProgress as u8
+    }
+
+    pub fn update_proposal_stage(&mut self, safe: &Safe) {
+        let approvals_required = safe.approvals_required as u16;
+        let approved_weight = safe.get_approval_weight(&self.approvals, true);
+        let rejected_weight = safe.get_approval_weight(&self.approvals, false);
+        if safe.approver_weight().saturating_sub(rejected_weight) < approvals_required {
+            self.proposal_stage = ProposalStateType::Rejected as u8;
+        }
+
+        if approved_weight >= approvals_required {
+            self.proposal_stage = ProposalStateType::Approved as u8;
+        }
+    }
+
     pub fn is_new_owner_approval(&self, owner: &Pubkey) -> bool {
         self.approvals
             .iter()
             .all(|approval| approval.owner != *owner)
     }
 
+    /// Prefers the caller's own vote over one they cast as a delegate
+    pub fn find_approval(&self, caller: &Pubkey) -> Option<usize> {
+        self.approvals
+            .iter()
+            .position(|approval| approval.owner == *caller)
+            .or_else(|| {
+                self.approvals
+                    .iter()
+                    .position(|approval| approval.delegate == *caller)
+            })
+    }
+
     pub fn validate_flow_data(&self) -> bool {
         if self.trigger_type != TriggerType::Manual as u8
             && self.trigger_type != TriggerType::Time as u8
@@ -137,6 +270,10 @@ impl Flow {
         if self.remaining_runs < 0 && self.remaining_runs != RECURRING_FOREVER {
             return false;
         }
+
+        if self.prerequisites.len() > MAX_FLOW_PREREQUISITES {
+            return false;
+        }
         true
     }
 
@@ -144,6 +281,28 @@ impl Flow {
         self.remaining_runs > 0 || self.remaining_runs == RECURRING_FOREVER
     }
 
+    /// Actions to run next, resuming after the last executed batch. A `batch_size` of 0 runs
+    /// every remaining action
+    pub fn next_action_batch(&self, batch_size: u16) -> Range<usize> {
+        let start = self.executed_actions as usize;
+        let end = if batch_size == 0 {
+            self.actions.len()
+        } else {
+            self.actions.len().min(start + batch_size as usize)
+        };
+        start..end
+    }
+
+    pub fn is_execution_resumable(&self) -> bool {
+        self.trigger_type == TriggerType::Manual as u8
+            && self.proposal_stage == ProposalStateType::ExecutionInProgress as u8
+    }
+
+    /// Expiry only stops a flow from starting, so a half-executed flow can still finish
+    pub fn is_expired_for_execute(&self, now: i64) -> bool {
+        !self.is_execution_resumable() && now > self.expiry_date
+    }
+
     pub fn is_due_for_execute(&self, now: i64) -> bool {
         if self.trigger_type == TriggerType::Program as u8 {
             return self.has_remaining_runs();
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+#[account]
+#[derive(Debug)]
+pub struct InstructionBuffer {
+    pub safe: Pubkey,
+    pub creator: Pubkey,
+    pub sealed: bool,
+    pub data_hash: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+impl InstructionBuffer {
+    pub fn space(capacity: usize) -> usize {
+        8    // Anchor account discriminator
+        + 32 // safe
+        + 32 // creator
+        + 1  // sealed
+        + 32 // data_hash
+        + 4 + capacity // data
+    }
+
+    /// Writes `chunk` at `offset`, which may overwrite earlier bytes or extend the data
+    pub fn write(&mut self, offset: usize, chunk: &[u8]) -> bool {
+        if offset > self.data.len() {
+            return false;
+        }
+        let end = offset + chunk.len();
+        if end > self.data.len() {
+            self.data.resize(end, 0);
+        }
+        self.data[offset..end].copy_from_slice(chunk);
+        true
+    }
+
+    pub fn seal(&mut self) {
+        self.sealed = true;
+        self.data_hash = hash(&self.data).to_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_and_seal() {
+        let mut buffer = InstructionBuffer {
+            safe: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            sealed: false,
+            data_hash: [0; 32],
+            data: vec![],
+        };
+
+        assert!(buffer.write(0, &[1, 2, 3]));
+        assert!(buffer.write(3, &[4, 5]));
+        assert!(buffer.write(1, &[9]));
+        assert!(!buffer.write(6, &[6]));
+        assert_eq!(buffer.data, vec![1, 9, 3, 4, 5]);
+
+        buffer.seal();
+        assert!(buffer.sealed);
+        assert_eq!(buffer.data_hash, hash(&[1, 9, 3, 4, 5]).to_bytes());
+        assert_eq!(
+            InstructionBuffer::space(buffer.data.len()),
+            8 + buffer.try_to_vec().unwrap().len()
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Action, ApprovalRecord, Flow, Safe, TargetAccountSpec, ALL_OWNER_ROLES};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct LegacyApprovalRecord {
+    pub owner: Pubkey,
+    pub date: i64,
+    pub is_approved: bool,
+}
+
+impl LegacyApprovalRecord {
+    pub fn into_approval_record(self) -> ApprovalRecord {
+        ApprovalRecord {
+            owner: self.owner,
+            date: self.date,
+            is_approved: self.is_approved,
+            delegate: Pubkey::default(),
+            content_hash: [0; 32],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct LegacyAction {
+    pub name: String,
+    pub action_code: u32,
+    pub instruction: Vec<u8>,
+    pub program: Pubkey,
+    pub accounts: Vec<TargetAccountSpec>,
+    pub extra: String,
+}
+
+impl LegacyAction {
+    pub fn into_action(self) -> Action {
+        Action {
+            name: self.name,
+            action_code: self.action_code,
+            instruction: self.instruction,
+            program: self.program,
+            accounts: self.accounts,
+            extra: self.extra,
+            instruction_buffer: None,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct LegacySafe {
+    pub approvals_required: u8,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub signer_bump: u8,
+    pub owner_set_seqno: u8,
+    pub extra: String,
+    pub owners: Vec<Pubkey>,
+}
+
+impl LegacySafe {
+    /// Legacy safes were allocated for `Safe::MAX_OWNERS` at creation and never resized
+    pub fn space(&self) -> usize {
+        8    // Anchor account discriminator
+        + 1  // approvals_required
+        + 32 // creator
+        + 8  // created_at
+        + 1  // signer_bump
+        + 1  // owner_set_seqno
+        + 4 + self.extra.len() // extra
+        + 4 + std::mem::size_of::<Pubkey>() * (Safe::MAX_OWNERS as usize) // owners
+    }
+
+    pub fn into_safe(self) -> Safe {
+        let total_owners = self.owners.len();
+        Safe {
+            approvals_required: self.approvals_required,
+            creator: self.creator,
+            created_at: self.created_at,
+            signer_bump: self.signer_bump,
+            owner_set_seqno: self.owner_set_seqno as u64,
+            max_owners: Safe::MAX_OWNERS,
+            extra: self.extra,
+            owners: self.owners,
+            owner_weights: vec![Safe::DEFAULT_OWNER_WEIGHT; total_owners],
+            owner_roles: vec![ALL_OWNER_ROLES; total_owners],
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct LegacyFlow {
+    pub requested_by: Pubkey,
+    pub safe: Pubkey,
+    pub last_updated_date: i64,
+    pub proposal_stage: u8,
+    pub created_date: i64,
+    pub trigger_type: u8,
+    pub next_execution_time: i64,
+    pub retry_window: u32,
+    pub recurring: bool,
+    pub remaining_runs: i16,
+    pub schedule_end_date: i64,
+    pub client_app_id: u32,
+    pub last_rent_charged: i64,
+    pub last_scheduled_execution: i64,
+    pub expiry_date: i64,
+    pub expire_on_complete: bool,
+    pub app_id: Pubkey,
+    pub pay_fee_from: u8,
+    pub user_utc_offset: i32,
+    pub custom_compute_budget: u32,
+    pub custom_fee: u32,
+    pub custom_field_1: i32,
+    pub custom_field_2: i32,
+    pub owner_set_seqno: u8,
+    pub external_id: String,
+    pub cron: String,
+    pub name: String,
+    pub extra: String,
+    pub actions: Vec<LegacyAction>,
+    pub approvals: Vec<LegacyApprovalRecord>,
+}
+
+impl LegacyFlow {
+    pub fn into_flow(self) -> Flow {
+        Flow {
+            requested_by: self.requested_by,
+            safe: self.safe,
+            last_updated_date: self.last_updated_date,
+            proposal_stage: self.proposal_stage,
+            created_date: self.created_date,
+            trigger_type: self.trigger_type,
+            next_execution_time: self.next_execution_time,
+            retry_window: self.retry_window,
+            recurring: self.recurring,
+            remaining_runs: self.remaining_runs,
+            schedule_end_date: self.schedule_end_date,
+            client_app_id: self.client_app_id,
+            last_rent_charged: self.last_rent_charged,
+            last_scheduled_execution: self.last_scheduled_execution,
+            expiry_date: self.expiry_date,
+            expire_on_complete: self.expire_on_complete,
+            app_id: self.app_id,
+            pay_fee_from: self.pay_fee_from,
+            user_utc_offset: self.user_utc_offset,
+            custom_compute_budget: self.custom_compute_budget,
+            custom_fee: self.custom_fee,
+            custom_field_1: self.custom_field_1,
+            custom_field_2: self.custom_field_2,
+            owner_set_seqno: self.owner_set_seqno as u64,
+            vault_index: 0,
+            // Legacy flows keep their keypair address and have no proposal number
+            proposal_index: 0,
+            executed_actions: 0,
+            approval_nonce: 0,
+            external_id: self.external_id,
+            cron: self.cron,
+            name: self.name,
+            extra: self.extra,
+            prerequisites: vec![],
+            actions: self
+                .actions
+                .into_iter()
+                .map(|action| action.into_action())
+                .collect(),
+            approvals: self
+                .approvals
+                .into_iter()
+                .map(|approval| approval.into_approval_record())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_safe_into_safe() {
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        let legacy_safe = LegacySafe {
+            approvals_required: 2,
+            creator: owner_a,
+            created_at: 1652946372,
+            signer_bump: 254,
+            owner_set_seqno: 255,
+            extra: "extra".to_string(),
+            owners: vec![owner_a, owner_b],
+        };
+
+        let safe = legacy_safe.into_safe();
+        assert_eq!(safe.owner_set_seqno, 255);
+        assert_eq!(safe.owners, vec![owner_a, owner_b]);
+        assert_eq!(safe.owner_weights, vec![1, 1]);
+        assert_eq!(safe.owner_roles, vec![ALL_OWNER_ROLES, ALL_OWNER_ROLES]);
+        assert_eq!(safe.max_owners, Safe::MAX_OWNERS);
+        assert_eq!(safe.approver_weight(), 2);
+        assert!(safe.can_manage_config());
+    }
+
+    #[test]
+    fn test_legacy_safe_space() {
+        let legacy_safe = LegacySafe {
+            approvals_required: 1,
+            creator: Pubkey::new_unique(),
+            created_at: 1652946372,
+            signer_bump: 254,
+            owner_set_seqno: 0,
+            extra: "extra".to_string(),
+            owners: vec![Pubkey::new_unique()],
+        };
+
+        assert_eq!(legacy_safe.space(), 8 + 1 + 32 + 8 + 1 + 1 + 4 + 5 + 4 + 32 * 64);
+        assert!(8 + legacy_safe.try_to_vec().unwrap().len() < legacy_safe.space());
+    }
+}
@@ -1,13 +1,25 @@
 pub mod action;
+pub mod allowance;
 pub mod approval_record;
+pub mod config_change;
+pub mod delegate;
 pub mod flow;
+pub mod instruction_buffer;
+pub mod legacy;
+pub mod recovery;
 pub mod safe;
 pub mod static_config;
 pub mod target_acount_spec;
 
 pub use action::*;
+pub use allowance::*;
 pub use approval_record::*;
+pub use config_change::*;
+pub use delegate::*;
 pub use flow::*;
+pub use instruction_buffer::*;
+pub use legacy::*;
+pub use recovery::*;
 pub use safe::*;
 pub use static_config::*;
 pub use target_acount_spec::*;
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Debug)]
+pub struct Recovery {
+    pub safe: Pubkey,
+    pub initiated_by: Pubkey,
+    pub initiated_at: i64,
+    pub effective_at: i64,
+    pub expires_at: i64,
+    pub approvals_required: u8,
+    pub new_owners: Vec<Pubkey>,
+    pub approvals: Vec<Pubkey>,
+}
+
+impl Recovery {
+    pub fn space(new_owners_count: usize, max_guardians: u8) -> usize {
+        8    // Anchor account discriminator
+        + 32 // safe
+        + 32 // initiated_by
+        + 8  // initiated_at
+        + 8  // effective_at
+        + 8  // expires_at
+        + 1  // approvals_required
+        + 4 + std::mem::size_of::<Pubkey>() * new_owners_count // new_owners
+        + 4 + std::mem::size_of::<Pubkey>() * (max_guardians as usize) // approvals
+    }
+
+    /// An expired recovery can no longer be executed and any guardian can close it
+    pub fn is_expired(&self, now: i64) -> bool {
+        now > self.expires_at
+    }
+
+    pub fn get_guardian_approvals(&self, guardians: &[Pubkey]) -> u8 {
+        self.approvals
+            .iter()
+            .filter(|approval| guardians.contains(approval))
+            .count() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_guardian_approvals() {
+        let guardian_a = Pubkey::new_unique();
+        let guardian_b = Pubkey::new_unique();
+        let guardian_c = Pubkey::new_unique();
+        let recovery = Recovery {
+            safe: Pubkey::new_unique(),
+            initiated_by: guardian_a,
+            initiated_at: 1652946372,
+            effective_at: 1653032772,
+            expires_at: 1653119172,
+            approvals_required: 1,
+            new_owners: vec![Pubkey::new_unique()],
+            approvals: vec![guardian_a, guardian_b],
+        };
+
+        assert_eq!(recovery.get_guardian_approvals(&[guardian_a, guardian_b]), 2);
+        assert_eq!(recovery.get_guardian_approvals(&[guardian_a, guardian_c]), 1);
+        assert_eq!(recovery.get_guardian_approvals(&[guardian_c]), 0);
+        assert!(!recovery.is_expired(1653119172));
+        assert!(recovery.is_expired(1653119173));
+    }
+}
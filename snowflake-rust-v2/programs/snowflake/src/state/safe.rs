@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
+use crate::state::{Allowance, ApprovalRecord, ConfigChange, OwnerRole, ALL_OWNER_ROLES};
 
 #[account]
 #[derive(Default, Debug)]
@@ -9,13 +10,38 @@ pub struct Safe {
     pub creator: Pubkey,
     pub created_at: i64,
     pub signer_bump: u8,
-    pub owner_set_seqno: u8,
+    pub owner_set_seqno: u64,
+    pub max_owners: u8,
     pub extra: String,
     pub owners: Vec<Pubkey>,
+    pub owner_weights: Vec<u8>,
+    pub owner_roles: Vec<u8>,
+    pub config_change_delay: u32,
+    pub config_change_seqno: u32,
+    pub pending_config_changes: Vec<ConfigChange>,
+    pub allowances: Vec<Allowance>,
+    pub guardian_threshold: u8,
+    pub recovery_delay: u32,
+    pub guardians: Vec<Pubkey>,
+    pub frozen: bool,
+    pub pauser: Pubkey,
+    /// Bitmap of the owners, by index, who approved unfreezing the safe
+    pub unfreeze_approvals: Vec<u8>,
+    pub vault_bumps: Vec<u8>,
+    pub active_flows: u32,
+    pub proposal_count: u64,
 }
 
 impl Safe {
     pub const MAX_OWNERS: u8 = 64;
+    /// `max_owners` is stored as a u8, so the owner cap can be raised to at most 254 owners.
+    pub const MAX_OWNERS_LIMIT: u8 = 254;
+    pub const DEFAULT_OWNER_WEIGHT: u8 = 1;
+    pub const MAX_PENDING_CONFIG_CHANGES: u8 = 8;
+    pub const MAX_CONFIG_CHANGE_DELAY: u32 = 30 * 24 * 60 * 60;
+    pub const MAX_ALLOWANCES: u8 = 16;
+    pub const MAX_GUARDIANS: u8 = 10;
+    pub const MAX_VAULTS: u8 = 8;
 
     pub fn space(max_owners: u8, extra_content: String) -> usize {
         8    // Anchor account discriminator
@@ -23,14 +49,187 @@ impl Safe {
         + 32 // creator
         + 8  // created_at
         + 1  // signer_bump
-        + 1  // owner_set_seqno
+        + 8  // owner_set_seqno
+        + 1  // max_owners
         + 4 + extra_content.len() // extra
         + 4 + std::mem::size_of::<Pubkey>() * (max_owners as usize) // owners
+        + 4 + (max_owners as usize) // owner_weights
+        + 4 + (max_owners as usize) // owner_roles
+        + 4  // config_change_delay
+        + 4  // config_change_seqno
+        + 4 + ConfigChange::SPACE * (Safe::MAX_PENDING_CONFIG_CHANGES as usize) // pending_config_changes
+        + 4 + Allowance::SPACE * (Safe::MAX_ALLOWANCES as usize) // allowances
+        + 1  // guardian_threshold
+        + 4  // recovery_delay
+        + 4 + std::mem::size_of::<Pubkey>() * (Safe::MAX_GUARDIANS as usize) // guardians
+        + 1  // frozen
+        + 32 // pauser
+        + 4 + (max_owners as usize) / 8 + 1 // unfreeze_approvals
+        + 4 + (Safe::MAX_VAULTS as usize) // vault_bumps
+        + 4  // active_flows
+        + 8  // proposal_count
     }
 
     pub fn is_owner(&self, caller: &Pubkey) -> bool {
         self.owners.contains(caller)
     }
+
+    pub fn is_guardian(&self, caller: &Pubkey) -> bool {
+        self.guardians.contains(caller)
+    }
+
+    pub fn owner_weight(&self, owner: &Pubkey) -> u8 {
+        self.owners
+            .iter()
+            .zip(self.owner_weights.iter())
+            .find(|(item, _)| *item == owner)
+            .map_or(0, |(_, weight)| *weight)
+    }
+
+    pub fn has_role(&self, owner: &Pubkey, role: OwnerRole) -> bool {
+        let role = role as u8;
+        self.owners
+            .iter()
+            .zip(self.owner_roles.iter())
+            .any(|(item, roles)| item == owner && roles & role != 0)
+    }
+
+    /// Every owner has exactly one weight and one roles entry, at the same index
+    pub fn assert_owner_data(&self) -> Result<()> {
+        require!(
+            self.owner_weights.len() == self.owners.len(),
+            ErrorCode::InvalidOwnerWeight
+        );
+        require!(
+            self.owner_roles.len() == self.owners.len(),
+            ErrorCode::InvalidOwnerRoles
+        );
+        Ok(())
+    }
+
+    pub fn can_manage_config(&self) -> bool {
+        self.owners
+            .iter()
+            .any(|owner| self.has_role(owner, OwnerRole::ManageConfig))
+    }
+
+    pub fn approver_weight(&self) -> u16 {
+        self.owners
+            .iter()
+            .filter(|owner| self.has_role(owner, OwnerRole::Approve))
+            .map(|owner| self.owner_weight(owner) as u16)
+            .sum()
+    }
+
+    // Vault 0 is the original safe signer, vault N is stored at vault_bumps[N - 1]
+    pub fn vault_bump(&self, vault_index: u8) -> Option<u8> {
+        if vault_index == 0 {
+            return Some(self.signer_bump);
+        }
+        self.vault_bumps.get(vault_index as usize - 1).copied()
+    }
+
+    pub fn release_flow(&mut self) {
+        self.active_flows = self.active_flows.saturating_sub(1);
+    }
+
+    pub fn find_config_change(&self, id: u32) -> Option<usize> {
+        self.pending_config_changes
+            .iter()
+            .position(|change| change.id == id)
+    }
+
+    pub fn find_allowance(&self, spender: &Pubkey, mint: &Pubkey) -> Option<usize> {
+        self.allowances
+            .iter()
+            .position(|allowance| allowance.spender == *spender && allowance.mint == *mint)
+    }
+
+    pub fn has_unfreeze_approval(&self, owner_index: usize) -> bool {
+        matches!(
+            self.unfreeze_approvals.get(owner_index / 8),
+            Some(bits) if bits & (1 << (owner_index % 8)) != 0
+        )
+    }
+
+    pub fn add_unfreeze_approval(&mut self, owner_index: usize) {
+        if self.unfreeze_approvals.len() <= owner_index / 8 {
+            self.unfreeze_approvals.resize(owner_index / 8 + 1, 0);
+        }
+        self.unfreeze_approvals[owner_index / 8] |= 1 << (owner_index % 8);
+    }
+
+    pub fn clear_unfreeze_approval(&mut self, owner_index: usize) {
+        if let Some(bits) = self.unfreeze_approvals.get_mut(owner_index / 8) {
+            *bits &= !(1 << (owner_index % 8));
+        }
+    }
+
+    /// Drops the approval of a removed owner and moves the owners after it down one index
+    pub fn remove_unfreeze_approval(&mut self, owner_index: usize) {
+        let approvals: Vec<usize> = (0..self.unfreeze_approvals.len() * 8)
+            .filter(|index| *index != owner_index && self.has_unfreeze_approval(*index))
+            .map(|index| if index > owner_index { index - 1 } else { index })
+            .collect();
+        self.unfreeze_approvals = Vec::new();
+        for index in approvals {
+            self.add_unfreeze_approval(index);
+        }
+    }
+
+    pub fn get_unfreeze_weight(&self) -> u16 {
+        self.owners
+            .iter()
+            .enumerate()
+            .filter(|(index, owner)| {
+                self.has_unfreeze_approval(*index) && self.has_role(owner, OwnerRole::Approve)
+            })
+            .map(|(_, owner)| self.owner_weight(owner) as u16)
+            .sum()
+    }
+
+    pub fn total_weight(&self) -> u16 {
+        self.owners
+            .iter()
+            .map(|owner| self.owner_weight(owner) as u16)
+            .sum()
+    }
+
+    /// Votes from owners who have since lost the Approve role no longer count
+    pub fn get_approval_weight(&self, approvals: &[ApprovalRecord], is_approved: bool) -> u16 {
+        approvals
+            .iter()
+            .filter(|approval| {
+                approval.is_approved == is_approved
+                    && self.has_role(&approval.owner, OwnerRole::Approve)
+            })
+            .map(|approval| self.owner_weight(&approval.owner) as u16)
+            .sum()
+    }
+}
+
+pub fn assert_owner_roles(owners: &[Pubkey], owner_roles: &[u8]) -> Result<()> {
+    require!(
+        owners.len() == owner_roles.len(),
+        ErrorCode::InvalidOwnerRoles
+    );
+    require!(
+        owner_roles.iter().all(|roles| roles & !ALL_OWNER_ROLES == 0),
+        ErrorCode::InvalidOwnerRoles
+    );
+    Ok(())
+}
+
+pub fn assert_owner_weights(owners: &[Pubkey], owner_weights: &[u8]) -> Result<()> {
+    require!(
+        owners.len() == owner_weights.len(),
+        ErrorCode::InvalidOwnerWeight
+    );
+    require!(
+        owner_weights.iter().all(|weight| *weight > 0),
+        ErrorCode::InvalidOwnerWeight
+    );
+    Ok(())
 }
 
 pub fn assert_unique_owners(owners: &[Pubkey]) -> Result<()> {
@@ -46,6 +245,7 @@ pub fn assert_unique_owners(owners: &[Pubkey]) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
 
     #[test]
     fn test_is_owner() {
@@ -63,6 +263,71 @@ mod tests {
         assert_eq!(safe.is_owner(&owner_c), false);
     }
 
+    #[test]
+    fn test_is_guardian() {
+        let mut safe = sample_safe();
+        let guardian_a = Pubkey::new_unique();
+        let guardian_b = Pubkey::new_unique();
+        safe.guardians = vec![guardian_a];
+
+        assert!(safe.is_guardian(&guardian_a));
+        assert!(!safe.is_guardian(&guardian_b));
+    }
+
+    #[test]
+    fn test_unfreeze_weight() {
+        let mut safe = sample_safe();
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        safe.owners = vec![owner_a, owner_b];
+        safe.owner_weights = vec![2, 1];
+        safe.owner_roles = vec![ALL_OWNER_ROLES, OwnerRole::Propose as u8];
+
+        safe.add_unfreeze_approval(1);
+        safe.add_unfreeze_approval(2);
+        assert_eq!(safe.get_unfreeze_weight(), 0);
+
+        safe.add_unfreeze_approval(0);
+        assert_eq!(safe.get_unfreeze_weight(), 2);
+
+        safe.clear_unfreeze_approval(0);
+        assert_eq!(safe.get_unfreeze_weight(), 0);
+    }
+
+    #[test]
+    fn test_remove_unfreeze_approval() {
+        let mut safe = sample_safe();
+        safe.add_unfreeze_approval(0);
+        safe.add_unfreeze_approval(3);
+        safe.add_unfreeze_approval(9);
+        assert_eq!(safe.unfreeze_approvals, vec![0b1001, 0b10]);
+
+        safe.remove_unfreeze_approval(3);
+        assert!(safe.has_unfreeze_approval(0));
+        assert!(!safe.has_unfreeze_approval(3));
+        assert!(safe.has_unfreeze_approval(8));
+        assert!(!safe.has_unfreeze_approval(9));
+
+        safe.remove_unfreeze_approval(0);
+        assert_eq!(safe.unfreeze_approvals, vec![0b10000000]);
+    }
+
+    #[test]
+    fn test_space_fits_in_a_single_allocation() {
+        assert!(Safe::space(Safe::MAX_OWNERS, String::new()) <= MAX_PERMITTED_DATA_INCREASE);
+    }
+
+    #[test]
+    fn test_vault_bump() {
+        let mut safe = sample_safe();
+        safe.vault_bumps = vec![253, 255];
+
+        assert_eq!(safe.vault_bump(0), Some(254));
+        assert_eq!(safe.vault_bump(1), Some(253));
+        assert_eq!(safe.vault_bump(2), Some(255));
+        assert_eq!(safe.vault_bump(3), None);
+    }
+
     #[test]
     fn test_assert_unique_owners() {
         let owner_a = Pubkey::new_unique();
@@ -81,6 +346,146 @@ mod tests {
         assert_eq!(result.is_err(), true);
     }
 
+    #[test]
+    fn test_owner_weights() {
+        let mut safe = sample_safe();
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        let owner_c = Pubkey::new_unique();
+        safe.owners = vec![owner_a, owner_b];
+        safe.owner_weights = vec![3, 1];
+        safe.owner_roles = vec![ALL_OWNER_ROLES, ALL_OWNER_ROLES];
+
+        assert_eq!(safe.owner_weight(&owner_a), 3);
+        assert_eq!(safe.owner_weight(&owner_b), 1);
+        assert_eq!(safe.owner_weight(&owner_c), 0);
+        assert_eq!(safe.total_weight(), 4);
+
+        let approvals = vec![
+            ApprovalRecord {
+                owner: owner_a,
+                date: 1652937049,
+                is_approved: true,
+                delegate: Pubkey::default(),
+                content_hash: [0; 32],
+            },
+            ApprovalRecord {
+                owner: owner_b,
+                date: 1652937050,
+                is_approved: false,
+                delegate: Pubkey::default(),
+                content_hash: [0; 32],
+            },
+        ];
+        assert_eq!(safe.get_approval_weight(&approvals, true), 3);
+        assert_eq!(safe.get_approval_weight(&approvals, false), 1);
+
+        safe.owner_roles[0] = OwnerRole::Propose as u8;
+        assert_eq!(safe.get_approval_weight(&approvals, true), 0);
+    }
+
+    #[test]
+    fn test_assert_owner_data() {
+        let mut safe = sample_safe();
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        safe.owners = vec![owner_a, owner_b];
+        safe.owner_weights = vec![3, 1];
+        safe.owner_roles = vec![ALL_OWNER_ROLES, ALL_OWNER_ROLES];
+        assert!(safe.assert_owner_data().is_ok());
+
+        safe.owner_weights = vec![3];
+        assert!(safe.assert_owner_data().is_err());
+        assert_eq!(safe.owner_weight(&owner_b), 0);
+
+        safe.owner_weights = vec![3, 1];
+        safe.owner_roles = vec![ALL_OWNER_ROLES];
+        assert!(safe.assert_owner_data().is_err());
+    }
+
+    #[test]
+    fn test_assert_owner_weights() {
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+
+        let result = assert_owner_weights(&[owner_a, owner_b], &[3, 1]);
+        assert_eq!(result.is_ok(), true);
+
+        let result = assert_owner_weights(&[owner_a, owner_b], &[3]);
+        assert_eq!(result.is_err(), true);
+
+        let result = assert_owner_weights(&[owner_a, owner_b], &[3, 0]);
+        assert_eq!(result.is_err(), true);
+    }
+
+    #[test]
+    fn test_owner_roles() {
+        let mut safe = sample_safe();
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        let owner_c = Pubkey::new_unique();
+        safe.owners = vec![owner_a, owner_b];
+        safe.owner_weights = vec![3, 1];
+        safe.owner_roles = vec![
+            ALL_OWNER_ROLES,
+            OwnerRole::Propose as u8 | OwnerRole::Execute as u8,
+        ];
+
+        assert!(safe.has_role(&owner_a, OwnerRole::ManageConfig));
+        assert!(safe.has_role(&owner_b, OwnerRole::Propose));
+        assert!(!safe.has_role(&owner_b, OwnerRole::Approve));
+        assert!(!safe.has_role(&owner_c, OwnerRole::Propose));
+        assert_eq!(safe.approver_weight(), 3);
+        assert!(safe.can_manage_config());
+
+        safe.owner_roles[0] = OwnerRole::Approve as u8 | OwnerRole::Execute as u8;
+        assert!(!safe.can_manage_config());
+    }
+
+    #[test]
+    fn test_assert_owner_roles() {
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+
+        let result = assert_owner_roles(&[owner_a, owner_b], &[ALL_OWNER_ROLES, 0]);
+        assert!(result.is_ok());
+
+        let result = assert_owner_roles(&[owner_a, owner_b], &[ALL_OWNER_ROLES]);
+        assert!(result.is_err());
+
+        let result = assert_owner_roles(&[owner_a], &[ALL_OWNER_ROLES + 1]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_find_config_change() {
+        let mut safe = sample_safe();
+        safe.pending_config_changes = vec![
+            sample_config_change(3, 1652946372),
+            sample_config_change(5, 1652946400),
+        ];
+
+        assert_eq!(safe.find_config_change(3), Some(0));
+        assert_eq!(safe.find_config_change(5), Some(1));
+        assert_eq!(safe.find_config_change(4), None);
+
+        let change = &safe.pending_config_changes[1];
+        assert!(!change.is_effective(1652946399));
+        assert!(change.is_effective(1652946400));
+    }
+
+    fn sample_config_change(id: u32, effective_at: i64) -> ConfigChange {
+        ConfigChange {
+            id,
+            change_type: 0,
+            owner: Pubkey::new_unique(),
+            value: 1,
+            queued_at: 1652946000,
+            effective_at,
+            payload_hash: ConfigChange::payload_hash(&[]),
+        }
+    }
+
     fn sample_safe() -> Safe {
         Safe {
             approvals_required: 1,
@@ -88,8 +493,24 @@ mod tests {
             created_at: 1652946372,
             signer_bump: 254,
             owner_set_seqno: 0,
+            max_owners: Safe::MAX_OWNERS,
             extra: "".to_string(),
             owners: vec![],
+            owner_weights: vec![],
+            owner_roles: vec![],
+            config_change_delay: 0,
+            config_change_seqno: 0,
+            pending_config_changes: vec![],
+            allowances: vec![],
+            guardian_threshold: 0,
+            recovery_delay: 0,
+            guardians: vec![],
+            frozen: false,
+            pauser: Pubkey::default(),
+            unfreeze_approvals: vec![],
+            vault_bumps: vec![],
+            active_flows: 0,
+            proposal_count: 0,
         }
     }
 }
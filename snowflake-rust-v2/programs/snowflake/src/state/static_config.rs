@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 pub enum TriggerType {
     Manual = 1,
     Time = 2,
@@ -15,17 +17,47 @@ pub enum ProposalStateType {
     Aborted = 6,
 }
 
+pub enum OwnerRole {
+    Propose = 1,
+    Approve = 2,
+    Execute = 4,
+    Abort = 8,
+    ManageConfig = 16,
+}
+
+pub enum ConfigChangeType {
+    AddOwner = 0,
+    RemoveOwner = 1,
+    ChangeThreshold = 2,
+    SetOwnerRoles = 3,
+    SetConfigChangeDelay = 4,
+    SetAllowance = 5,
+    SetMaxOwners = 6,
+    SetExtra = 7,
+    SetGuardians = 8,
+    SetPauser = 9,
+    SetOwners = 10,
+    SwapOwner = 11,
+}
+
 pub enum FeeSource {
     FromFeeAccount = 0,
     FromFlow = 1,
 }
 
+pub const NATIVE_SOL_MINT: Pubkey = Pubkey::new_from_array([0; 32]);
+pub const SAFE_PREFIX: &[u8; 4] = b"Safe";
+pub const RECOVERY_PREFIX: &[u8; 8] = b"Recovery";
 pub const SAFE_SIGNER_PREFIX: &[u8; 10] = b"SafeSigner";
+pub const FLOW_PREFIX: &[u8; 4] = b"Flow";
+pub const DELEGATE_PREFIX: &[u8; 8] = b"Delegate";
 pub const DEFAULT_FLOW_EXPIRY_DURATION: i64 = 60 * 24 * 60 * 60;
 pub const MAXIMUM_REMAINING_RUNS_FOR_PROGRAM_TRIGGER: i16 = 1000;
 pub const RECURRING_FOREVER: i16 = -999;
 pub const DEFAULT_RETRY_WINDOW: u32 = 300;
+pub const MAX_FLOW_PREREQUISITES: usize = 8;
 pub const FLOW_EXECUTION_FEE: u64 = 50_000;
+pub const ALL_OWNER_ROLES: u8 = 0b11111;
 
 pub const TIMED_FLOW_COMPLETE: i64 = 0;
 pub const TIMED_FLOW_ERROR: i64 = -1;
@@ -7,6 +7,12 @@ pub struct TargetAccountSpec {
     pub is_writable: bool,
 }
 
+impl TargetAccountSpec {
+    pub const SPACE: usize = 32 // pubkey
+        + 1  // is_signer
+        + 1; // is_writable
+}
+
 impl From<&TargetAccountSpec> for AccountMeta {
     fn from(item: &TargetAccountSpec) -> Self {
         AccountMeta {
@@ -16,3 +22,13 @@ impl From<&TargetAccountSpec> for AccountMeta {
         }
     }
 }
+
+impl From<&AccountMeta> for TargetAccountSpec {
+    fn from(meta: &AccountMeta) -> Self {
+        TargetAccountSpec {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        }
+    }
+}
@@ -2,9 +2,11 @@
 mod tests {
     use anchor_lang::prelude::*;
 
+    use crate::common::parse_ed25519_signatures;
+    use crate::instructions::{approve_proposal_action, assert_valid_owner_set};
     use crate::state::approval_record::ApprovalRecord;
     use crate::state::static_config::*;
-    use crate::state::Flow;
+    use crate::state::{Action, Flow, OwnerSetChange, Safe, TargetAccountSpec};
 
     #[test]
     fn test_apply_flow_data() {
@@ -20,6 +22,18 @@ mod tests {
         assert!(flow.apply_flow_data(client_flow, now).is_err());
     }
 
+    #[test]
+    fn test_validate_prerequisites() {
+        let mut flow = sample_recurring_timed_flow();
+        flow.prerequisites = (0..MAX_FLOW_PREREQUISITES)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert!(flow.validate_flow_data());
+
+        flow.prerequisites.push(Pubkey::new_unique());
+        assert!(!flow.validate_flow_data());
+    }
+
     #[test]
     fn test_update_schedule_for_a_recurring_timed_flow_after_a_successful_run() {
         let mut flow = sample_recurring_timed_flow();
@@ -113,12 +127,302 @@ mod tests {
             owner: owner_b,
             date: 1652937049,
             is_approved: false,
+            delegate: Pubkey::default(),
+            content_hash: [0; 32],
         }];
         assert_eq!(flow.is_new_owner_approval(&owner_a), true);
         assert_eq!(flow.is_new_owner_approval(&owner_b), false);
         assert_eq!(flow.is_new_owner_approval(&owner_c), true);
     }
 
+    #[test]
+    fn test_is_active() {
+        let mut flow = sample_recurring_timed_flow();
+
+        flow.proposal_stage = ProposalStateType::Pending as u8;
+        assert!(flow.is_active());
+
+        flow.proposal_stage = ProposalStateType::ExecutionInProgress as u8;
+        assert!(flow.is_active());
+
+        flow.proposal_stage = ProposalStateType::Rejected as u8;
+        assert!(!flow.is_active());
+
+        flow.proposal_stage = ProposalStateType::Complete as u8;
+        assert!(!flow.is_active());
+    }
+
+    #[test]
+    fn test_update_proposal_stage() {
+        let mut flow = sample_recurring_timed_flow();
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        let safe = Safe {
+            approvals_required: 2,
+            owners: vec![owner_a, owner_b],
+            owner_weights: vec![1, 1],
+            owner_roles: vec![ALL_OWNER_ROLES, ALL_OWNER_ROLES],
+            ..Default::default()
+        };
+
+        flow.proposal_stage = ProposalStateType::Pending as u8;
+        flow.approvals = vec![ApprovalRecord {
+            owner: owner_a,
+            date: 1652937049,
+            is_approved: true,
+            delegate: Pubkey::default(),
+            content_hash: [0; 32],
+        }];
+        flow.update_proposal_stage(&safe);
+        assert_eq!(flow.proposal_stage, ProposalStateType::Pending as u8);
+
+        flow.approvals.push(ApprovalRecord {
+            owner: owner_b,
+            date: 1652937050,
+            is_approved: false,
+            delegate: Pubkey::default(),
+            content_hash: [0; 32],
+        });
+        flow.update_proposal_stage(&safe);
+        assert_eq!(flow.proposal_stage, ProposalStateType::Rejected as u8);
+
+        flow.proposal_stage = ProposalStateType::Pending as u8;
+        flow.approvals[1].is_approved = true;
+        flow.update_proposal_stage(&safe);
+        assert_eq!(flow.proposal_stage, ProposalStateType::Approved as u8);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let flow = sample_recurring_timed_flow();
+        let content_hash = flow.content_hash();
+        assert_eq!(content_hash, sample_recurring_timed_flow().content_hash());
+
+        let mut changed = sample_recurring_timed_flow();
+        changed.cron = String::from("0 11 1 * *");
+        assert_ne!(changed.content_hash(), content_hash);
+
+        let mut changed = sample_recurring_timed_flow();
+        changed.vault_index = 1;
+        assert_ne!(changed.content_hash(), content_hash);
+
+        let mut changed = sample_recurring_timed_flow();
+        changed.prerequisites = vec![Pubkey::new_unique()];
+        assert_ne!(changed.content_hash(), content_hash);
+
+        // Bookkeeping fields do not affect what executes
+        let mut changed = sample_recurring_timed_flow();
+        changed.name = String::from("Renamed");
+        changed.last_updated_date = 1652937049;
+        assert_eq!(changed.content_hash(), content_hash);
+    }
+
+    #[test]
+    fn test_flow_space() {
+        let mut flow = sample_recurring_timed_flow();
+        flow.name = String::from("Sample job");
+        flow.prerequisites = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        flow.actions = vec![Action {
+            name: String::from("Transfer"),
+            action_code: 0,
+            instruction: vec![2, 0, 0, 0, 64, 66, 15, 0, 0, 0, 0, 0],
+            program: Pubkey::new_unique(),
+            accounts: vec![
+                TargetAccountSpec {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: true,
+                    is_writable: true,
+                },
+                TargetAccountSpec {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: true,
+                },
+            ],
+            extra: String::new(),
+            instruction_buffer: Some(Pubkey::new_unique()),
+        }];
+        flow.approvals = (0..3)
+            .map(|_| ApprovalRecord {
+                owner: Pubkey::new_unique(),
+                date: 1652937049,
+                is_approved: true,
+                delegate: Pubkey::default(),
+                content_hash: [0; 32],
+            })
+            .collect();
+
+        assert_eq!(flow.space(3), 8 + flow.try_to_vec().unwrap().len());
+    }
+
+    #[test]
+    fn test_action_modifies_account() {
+        let safe = Pubkey::new_unique();
+        let mut action = Action {
+            name: String::new(),
+            action_code: 0,
+            instruction: vec![],
+            program: Pubkey::new_unique(),
+            accounts: vec![TargetAccountSpec {
+                pubkey: safe,
+                is_signer: false,
+                is_writable: false,
+            }],
+            extra: String::new(),
+            instruction_buffer: None,
+        };
+        assert!(!action.modifies_account(&safe));
+
+        action.accounts[0].is_writable = true;
+        assert!(action.modifies_account(&safe));
+        assert!(!action.modifies_account(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_assert_valid_owner_set() {
+        let safe = Safe {
+            max_owners: Safe::MAX_OWNERS,
+            ..Safe::default()
+        };
+        let owners = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let change = OwnerSetChange::new(owners.clone(), vec![], vec![], 2);
+        assert_eq!(change.owner_weights, vec![Safe::DEFAULT_OWNER_WEIGHT; 2]);
+        assert!(assert_valid_owner_set(&safe, &change).is_ok());
+
+        let change = OwnerSetChange::new(owners.clone(), vec![3, 1], vec![], 4);
+        assert!(assert_valid_owner_set(&safe, &change).is_ok());
+
+        let change = OwnerSetChange::new(owners.clone(), vec![3, 1], vec![], 5);
+        assert!(assert_valid_owner_set(&safe, &change).is_err());
+
+        let roles = vec![ALL_OWNER_ROLES, OwnerRole::Propose as u8];
+        let change = OwnerSetChange::new(owners.clone(), vec![], roles, 2);
+        assert!(assert_valid_owner_set(&safe, &change).is_err());
+
+        let change = OwnerSetChange::new(owners, vec![1], vec![], 1);
+        assert!(assert_valid_owner_set(&safe, &change).is_err());
+    }
+
+    #[test]
+    fn test_approve_proposal_action() {
+        let parent_safe = Pubkey::new_unique();
+        let parent_signer = Pubkey::new_unique();
+        let child_safe = Pubkey::new_unique();
+        let child_flow = Pubkey::new_unique();
+        let action =
+            approve_proposal_action(child_safe, child_flow, parent_signer, true, [7; 32]);
+
+        assert_eq!(action.program, crate::ID);
+        assert!(action.modifies_account(&child_safe));
+        assert!(action.modifies_account(&child_flow));
+        assert!(!action.modifies_account(&parent_safe));
+
+        // The parent signer is read-only, so the executing flow only lends it a signature
+        let caller = &action.accounts[2];
+        assert_eq!(caller.pubkey, parent_signer);
+        assert!(caller.is_signer);
+        assert!(!caller.is_writable);
+
+        let instruction =
+            crate::instruction::ApproveProposal::try_from_slice(&action.instruction[8..]).unwrap();
+        assert!(instruction.is_approved);
+        assert_eq!(instruction.content_hash, [7; 32]);
+        assert_eq!(instruction.owner, parent_signer);
+    }
+
+    #[test]
+    fn test_next_action_batch() {
+        let mut flow = sample_recurring_timed_flow();
+        flow.actions = (0..5)
+            .map(|_| Action {
+                name: String::new(),
+                action_code: 0,
+                instruction: vec![],
+                program: Pubkey::new_unique(),
+                accounts: vec![],
+                extra: String::new(),
+                instruction_buffer: None,
+            })
+            .collect();
+
+        assert_eq!(flow.next_action_batch(0), 0..5);
+        assert_eq!(flow.next_action_batch(2), 0..2);
+        flow.executed_actions = 4;
+        assert_eq!(flow.next_action_batch(2), 4..5);
+        assert_eq!(flow.next_action_batch(0), 4..5);
+        flow.executed_actions = 5;
+        assert!(flow.next_action_batch(2).is_empty());
+    }
+
+    #[test]
+    fn test_is_expired_for_execute() {
+        let mut flow = sample_recurring_timed_flow();
+        flow.trigger_type = TriggerType::Manual as u8;
+        flow.expiry_date = 1652946372;
+
+        flow.proposal_stage = ProposalStateType::Approved as u8;
+        assert!(!flow.is_expired_for_execute(1652946372));
+        assert!(flow.is_expired_for_execute(1652946373));
+
+        flow.proposal_stage = ProposalStateType::ExecutionInProgress as u8;
+        assert!(!flow.is_expired_for_execute(1652946373));
+    }
+
+    #[test]
+    fn test_reorder_actions() {
+        let mut flow = sample_recurring_timed_flow();
+        flow.actions = ["a", "b", "c"]
+            .iter()
+            .map(|name| Action {
+                name: name.to_string(),
+                action_code: 0,
+                instruction: vec![],
+                program: Pubkey::new_unique(),
+                accounts: vec![],
+                extra: String::new(),
+                instruction_buffer: None,
+            })
+            .collect();
+
+        assert!(flow.reorder_actions(&[2, 0, 1]).is_ok());
+        let names: Vec<&str> = flow.actions.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, vec!["c", "a", "b"]);
+
+        assert!(flow.reorder_actions(&[0, 1]).is_err());
+        assert!(flow.reorder_actions(&[0, 0, 1]).is_err());
+        assert!(flow.reorder_actions(&[0, 1, 3]).is_err());
+    }
+
+    #[test]
+    fn test_find_approval() {
+        let mut flow = sample_recurring_timed_flow();
+        let owner_a = Pubkey::new_unique();
+        let owner_b = Pubkey::new_unique();
+        flow.approvals = vec![
+            ApprovalRecord {
+                owner: owner_b,
+                date: 1652937049,
+                is_approved: true,
+                delegate: owner_a,
+                content_hash: [0; 32],
+            },
+            ApprovalRecord {
+                owner: owner_a,
+                date: 1652937050,
+                is_approved: true,
+                delegate: Pubkey::default(),
+                content_hash: [0; 32],
+            },
+        ];
+
+        assert_eq!(flow.find_approval(&owner_a), Some(1));
+        assert_eq!(flow.find_approval(&owner_b), Some(0));
+        flow.approvals.remove(1);
+        assert_eq!(flow.find_approval(&owner_a), Some(0));
+        assert_eq!(flow.find_approval(&Pubkey::new_unique()), None);
+    }
+
     #[test]
     fn test_calculate_next_execution_time() {
         let mut flow = sample_recurring_timed_flow();
@@ -137,6 +441,52 @@ mod tests {
         assert_eq!(flow.next_execution_time, 1661403600);
     }
 
+    #[test]
+    fn test_approval_message_changes_with_nonce() {
+        let flow = Pubkey::new_unique();
+        let message = ApprovalRecord::message(&flow, 3, 0, &[7; 32], true);
+
+        assert_eq!(message, ApprovalRecord::message(&flow, 3, 0, &[7; 32], true));
+        assert_ne!(message, ApprovalRecord::message(&flow, 3, 1, &[7; 32], true));
+        assert_ne!(message, ApprovalRecord::message(&flow, 3, 0, &[7; 32], false));
+    }
+
+    #[test]
+    fn test_parse_ed25519_signatures() {
+        let signer = Pubkey::new_unique();
+        let message = ApprovalRecord::message(&Pubkey::new_unique(), 3, 0, &[7; 32], true);
+
+        // Same layout as the Ed25519 precompile: header, offsets, pubkey, signature, message
+        let pubkey_offset: u16 = 16;
+        let signature_offset: u16 = pubkey_offset + 32;
+        let message_offset: u16 = signature_offset + 64;
+        let mut data = vec![1u8, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data.extend_from_slice(&message);
+
+        let signatures = parse_ed25519_signatures(&data).unwrap();
+        assert_eq!(signatures, vec![(signer, message)]);
+
+        // Keys stored in another instruction are not trusted
+        data[8] = 0;
+        data[9] = 0;
+        assert!(parse_ed25519_signatures(&data).is_err());
+
+        assert!(parse_ed25519_signatures(&[1u8, 0]).is_err());
+    }
+
     fn sample_recurring_timed_flow() -> Flow {
         Flow {
             requested_by: Pubkey::new_unique(),
@@ -164,11 +514,16 @@ mod tests {
             cron: String::from("0 10 1 * *"),
             name: "".to_string(),
             extra: "".to_string(),
+            prerequisites: vec![],
             actions: vec![],
             safe: Pubkey::new_unique(),
             approvals: vec![],
             proposal_stage: 0,
             owner_set_seqno: 0,
+            vault_index: 0,
+            proposal_index: 0,
+            executed_actions: 0,
+            approval_nonce: 0,
         }
     }
 }
@@ -1,4 +1,3 @@
-import * as anchor from '@project-serum/anchor';
 import { SystemProgram } from '@solana/web3.js';
 import { assert } from 'chai';
 import { JobBuilder } from '@snowflake-so/snowflake-sdk';
@@ -13,6 +12,7 @@ import {
   safeService,
   ownerB,
   SafeData,
+  EXECUTE_ALL_ACTIONS,
 } from './helper';
 
 describe('Flow - Abort', () => {
@@ -36,19 +36,15 @@ describe('Flow - Abort', () => {
       .scheduleCron('0 10 1 * *')
       .build();
 
-    const sendSolFlow = await createSampleFlowWithJob(
-      anchor.web3.Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      job
-    );
+    const sendSolFlow = await createSampleFlowWithJob(safeData.ctx.accounts.safe, job);
 
     const abortData = SafeInstructionService.abortFlowIxBase(
-      sendSolFlow.flowKeypair.publicKey,
+      sendSolFlow.flowAddress,
       safeData.ctx.accounts.safe,
       anchorProvider.wallet.publicKey
     );
 
-    let flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+    let flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
 
     assert.strictEqual(flowAccount.triggerType, TriggerType.Time);
     assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Pending);
@@ -60,26 +56,26 @@ describe('Flow - Abort', () => {
       assert.strictEqual(error.error.errorCode.code, 'RequestIsNotExecutedYet');
     }
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sendSolFlow.flowKeypair.publicKey,
+      sendSolFlow.flowAddress,
       true
     );
     await approveData.builder.rpc();
-    flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+    flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
     assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
 
     await program.methods
-      .executeMultisigFlow()
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
       .accounts(sendSolFlow.executeData.ctx.accounts)
       .remainingAccounts(sendSolFlow.executeData.ctx.remainingAccounts)
       .rpc();
-    flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+    flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
     assert.strictEqual(flowAccount.proposalStage, ProposalStateType.ExecutionInProgress);
 
     await program.methods.abortFlow().accounts(abortData.ctx.accounts).rpc();
-    flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+    flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
     assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Aborted);
   });
 });
@@ -1,6 +1,6 @@
-import { Keypair } from '@solana/web3.js';
 import assert from 'assert';
 import { expect } from 'chai';
+import { JobBuilder } from '@snowflake-so/snowflake-sdk';
 
 import {
   program,
@@ -11,6 +11,7 @@ import {
   ownerB,
   SafeData,
   ownerC,
+  ownerD,
   createSampleFlowDraft,
 } from './helper';
 import { ProposalStateType } from './models/flow';
@@ -25,13 +26,9 @@ describe('Flow - Action', () => {
 
   it('Can add actions', async () => {
     // create flow
-    const sampleFlowData = await createSampleFlowDraft(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      []
-    );
+    const sampleFlowData = await createSampleFlowDraft(safeData.ctx.accounts.safe, []);
 
-    let flowAccount = await program.account.flow.fetch(sampleFlowData.flowKeypair.publicKey);
+    let flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
     expect(flowAccount.proposalStage).to.equal(ProposalStateType.Draft);
     expect(flowAccount.actions).to.be.an('array').that.is.empty;
 
@@ -42,22 +39,23 @@ describe('Flow - Action', () => {
     );
     await safeService
       .createAddAction(
-        sampleFlowData.flowKeypair.publicKey,
+        safeData.ctx.accounts.safe,
+        sampleFlowData.flowAddress,
         anchorProvider.wallet.publicKey,
         ixs[0],
         true
       )
       .builder.rpc();
-    flowAccount = await program.account.flow.fetch(sampleFlowData.flowKeypair.publicKey);
+    flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
     expect(flowAccount.proposalStage).to.equal(ProposalStateType.Pending);
     expect(flowAccount.actions).to.be.an('array').that.is.lengthOf(1);
 
     // TODO fix execution error
     // // approve flow
-    // const approveData = safeService.approveProposal(
+    // const approveData = await safeService.approveProposal(
     //   anchorProvider.wallet.publicKey,
     //   safeData.ctx.accounts.safe,
-    //   sampleFlowData.flowKeypair.publicKey,
+    //   sampleFlowData.flowAddress,
     //   true
     // );
     // await approveData.builder.rpc();
@@ -65,13 +63,13 @@ describe('Flow - Action', () => {
     // try {
     //   // but can't execute
     //   const result = await program.methods
-    //     .executeMultisigFlow()
+    //     .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
     //     .accounts(sampleFlowData.executeData.ctx.accounts)
     //     .remainingAccounts(sampleFlowData.executeData.ctx.remainingAccounts)
     //     .rpc();
     //   console.log('result', result);
     //   flowAccount = await program.account.flow.fetch(
-    //     sampleFlowData.flowKeypair.publicKey
+    //     sampleFlowData.flowAddress
     //   );
     //   console.log('flowAccount', flowAccount);
     // } catch (error) {
@@ -81,16 +79,12 @@ describe('Flow - Action', () => {
   });
 
   it('Cannot add action if proposalStage is approved', async () => {
-    const sampleFlowData = await createSampleFlow(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      []
-    );
+    const sampleFlowData = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlowData.flowKeypair.publicKey,
+      sampleFlowData.flowAddress,
       true
     );
     await approveData.builder.rpc();
@@ -102,7 +96,8 @@ describe('Flow - Action', () => {
       );
       await safeService
         .createAddAction(
-          sampleFlowData.flowKeypair.publicKey,
+          safeData.ctx.accounts.safe,
+          sampleFlowData.flowAddress,
           anchorProvider.wallet.publicKey,
           ixs[0],
           true
@@ -114,16 +109,12 @@ describe('Flow - Action', () => {
   });
 
   it('Cannot add action if proposalStage is rejected', async () => {
-    const sampleFlowData = await createSampleFlow(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      []
-    );
+    const sampleFlowData = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlowData.flowKeypair.publicKey,
+      sampleFlowData.flowAddress,
       false
     );
     await approveData.builder.rpc();
@@ -135,7 +126,8 @@ describe('Flow - Action', () => {
       );
       await safeService
         .createAddAction(
-          sampleFlowData.flowKeypair.publicKey,
+          safeData.ctx.accounts.safe,
+          sampleFlowData.flowAddress,
           anchorProvider.wallet.publicKey,
           ixs[0],
           true
@@ -147,13 +139,9 @@ describe('Flow - Action', () => {
   });
 
   it('Only flow creator can add action', async () => {
-    const sampleFlowData = await createSampleFlowDraft(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      []
-    );
+    const sampleFlowData = await createSampleFlowDraft(safeData.ctx.accounts.safe, []);
 
-    const flowAccount = await program.account.flow.fetch(sampleFlowData.flowKeypair.publicKey);
+    const flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
     expect(flowAccount.proposalStage).to.equal(ProposalStateType.Draft);
     expect(flowAccount.actions).to.be.an('array').that.is.empty;
 
@@ -163,11 +151,167 @@ describe('Flow - Action', () => {
         ownerC.publicKey
       );
       await safeService
-        .createAddAction(sampleFlowData.flowKeypair.publicKey, ownerB.publicKey, ixs[0], true)
+        .createAddAction(
+          safeData.ctx.accounts.safe,
+          sampleFlowData.flowAddress,
+          ownerB.publicKey,
+          ixs[0],
+          true
+        )
         .builder.signers([ownerB])
         .rpc();
     } catch (error) {
       assert.strictEqual(error.error.errorCode.code, 'ConstraintHasOne');
     }
   });
+
+  it('Flow account grows as actions are added', async () => {
+    const job = new JobBuilder().jobName('Growing draft').jobInstructions([]).build();
+    const flowData = await safeService.createFlow(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe,
+      job.toSerializableJob()
+    );
+    await program.methods
+      .createFlow(0, flowData.serializableJob, true)
+      .accounts(flowData.ctx.accounts)
+      .rpc();
+    const initialAccount = await anchorProvider.connection.getAccountInfo(flowData.flowAddress);
+
+    const ixs = await safeService.createAddOwnerInstruction(
+      safeData.ctx.accounts.safe,
+      ownerC.publicKey
+    );
+    for (let i = 0; i < 3; i++) {
+      await safeService
+        .createAddAction(
+          safeData.ctx.accounts.safe,
+          flowData.flowAddress,
+          anchorProvider.wallet.publicKey,
+          ixs[0],
+          false
+        )
+        .builder.rpc();
+    }
+
+    const grownAccount = await anchorProvider.connection.getAccountInfo(flowData.flowAddress);
+    assert.ok(grownAccount.data.length > initialAccount.data.length);
+    const flowAccount = await program.account.flow.fetch(flowData.flowAddress);
+    expect(flowAccount.actions).to.be.an('array').that.is.lengthOf(3);
+  });
+
+  describe('Edit Actions', () => {
+    const addActions = async (flowAddress, owners) => {
+      for (const owner of owners) {
+        const ixs = await safeService.createAddOwnerInstruction(safeData.ctx.accounts.safe, owner);
+        await safeService
+          .createAddAction(
+            safeData.ctx.accounts.safe,
+            flowAddress,
+            anchorProvider.wallet.publicKey,
+            ixs[0],
+            false
+          )
+          .builder.rpc();
+      }
+    };
+    const actionData = async flowAddress => {
+      const flowAccount = await program.account.flow.fetch(flowAddress);
+      return (flowAccount.actions as any).map(action => Buffer.from(action.instruction));
+    };
+
+    it('Can replace, remove and reorder actions', async () => {
+      const sampleFlowData = await createSampleFlowDraft(safeData.ctx.accounts.safe, []);
+      const flowAddress = sampleFlowData.flowAddress;
+      await addActions(flowAddress, [ownerB.publicKey, ownerC.publicKey, ownerD.publicKey]);
+      const [actionB, actionC, actionD] = await actionData(flowAddress);
+
+      await safeService
+        .createReorderActions(
+          safeData.ctx.accounts.safe,
+          flowAddress,
+          anchorProvider.wallet.publicKey,
+          [2, 0, 1]
+        )
+        .builder.rpc();
+      assert.deepStrictEqual(await actionData(flowAddress), [actionD, actionB, actionC]);
+
+      await safeService
+        .createRemoveAction(
+          safeData.ctx.accounts.safe,
+          flowAddress,
+          anchorProvider.wallet.publicKey,
+          1
+        )
+        .builder.rpc();
+      assert.deepStrictEqual(await actionData(flowAddress), [actionD, actionC]);
+
+      const ixs = await safeService.createAddOwnerInstruction(
+        safeData.ctx.accounts.safe,
+        ownerB.publicKey
+      );
+      await safeService
+        .createReplaceAction(
+          safeData.ctx.accounts.safe,
+          flowAddress,
+          anchorProvider.wallet.publicKey,
+          0,
+          ixs[0]
+        )
+        .builder.rpc();
+      assert.deepStrictEqual(await actionData(flowAddress), [actionB, actionC]);
+    });
+
+    it('Rejects invalid indexes and orders', async () => {
+      const sampleFlowData = await createSampleFlowDraft(safeData.ctx.accounts.safe, []);
+      const flowAddress = sampleFlowData.flowAddress;
+      await addActions(flowAddress, [ownerB.publicKey, ownerC.publicKey]);
+
+      try {
+        await safeService
+          .createRemoveAction(
+            safeData.ctx.accounts.safe,
+            flowAddress,
+            anchorProvider.wallet.publicKey,
+            2
+          )
+          .builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidActionIndex');
+      }
+
+      try {
+        await safeService
+          .createReorderActions(
+            safeData.ctx.accounts.safe,
+            flowAddress,
+            anchorProvider.wallet.publicKey,
+            [0, 0]
+          )
+          .builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidActionOrder');
+      }
+    });
+
+    it('Cannot edit actions once submitted', async () => {
+      const sampleFlowData = await createSampleFlow(safeData.ctx.accounts.safe, []);
+
+      try {
+        await safeService
+          .createReorderActions(
+            safeData.ctx.accounts.safe,
+            sampleFlowData.flowAddress,
+            anchorProvider.wallet.publicKey,
+            []
+          )
+          .builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'FlowMustBeInDraftedBeforeUpdate');
+      }
+    });
+  });
 });
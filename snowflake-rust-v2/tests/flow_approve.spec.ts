@@ -1,12 +1,14 @@
 import { BN } from '@project-serum/anchor';
-import { Keypair } from '@solana/web3.js';
 import { assert } from 'chai';
 
+import { ProposalStateType } from './models/flow';
+import SafeInstructionService from './services/safeInstructionService';
 import {
   program,
   anchorProvider,
   createSampleSafe,
   createSampleFlow,
+  executeSampleFlow,
   createAddOwnerJob,
   safeService,
   delay,
@@ -16,6 +18,7 @@ import {
   ownerD,
   SafeData,
   createSampleFlowWithJobWithExpiryDate,
+  EXECUTE_ALL_ACTIONS,
 } from './helper';
 
 describe('Flow - Approve', () => {
@@ -27,34 +30,62 @@ describe('Flow - Approve', () => {
   });
 
   it('Can reject a flow', async () => {
-    const sampleFlowData = await createSampleFlow(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      []
-    );
+    const sampleFlowData = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlowData.flowKeypair.publicKey,
+      sampleFlowData.flowAddress,
       false
     );
     await approveData.builder.rpc();
 
-    const flowAccount = await program.account.flow.fetch(sampleFlowData.flowKeypair.publicKey);
+    const flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
 
     assert.strictEqual((flowAccount.approvals as any).length, 1);
     assert.ok(flowAccount.approvals[0].owner.equals(anchorProvider.wallet.publicKey));
     assert.strictEqual(flowAccount.approvals[0].isApproved, false);
   });
 
+  it('Approval is decided by owner weights', async () => {
+    const weightedSafeData = await createSampleSafe(owners, 3, [3, 1]);
+    const sampleFlow = await createSampleFlow(weightedSafeData.ctx.accounts.safe, []);
+
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      weightedSafeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      true
+    );
+    await approveData.builder.rpc();
+
+    const flowAccount = await program.account.flow.fetch(sampleFlow.flowAddress);
+    assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
+  });
+
+  it('Rejection is decided by owner weights', async () => {
+    const weightedSafeData = await createSampleSafe(owners, 2, [3, 1]);
+    const sampleFlow = await createSampleFlow(weightedSafeData.ctx.accounts.safe, []);
+
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      weightedSafeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      false
+    );
+    await approveData.builder.rpc();
+
+    const flowAccount = await program.account.flow.fetch(sampleFlow.flowAddress);
+    assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Rejected);
+  });
+
   it('Caller must be an owner', async () => {
-    const sampleFlow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, []);
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       ownerC.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlow.flowKeypair.publicKey,
+      sampleFlow.flowAddress,
       true
     );
 
@@ -67,12 +98,12 @@ describe('Flow - Approve', () => {
   });
 
   it('Caller has not approved the flow prior to the operation', async () => {
-    const sampleFlow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, []);
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlow.flowKeypair.publicKey,
+      sampleFlow.flowAddress,
       true
     );
     await approveData.builder.rpc();
@@ -91,17 +122,16 @@ describe('Flow - Approve', () => {
     const now = await getClusterUnixTimestamp();
     const delaySeconds = 3;
     const sampleFlow = await createSampleFlowWithJobWithExpiryDate(
-      Keypair.generate(),
       safeData.ctx.accounts.safe,
       job,
       new BN(now + delaySeconds)
     );
 
     try {
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        sampleFlow.flowKeypair.publicKey,
+        sampleFlow.flowAddress,
         true
       );
       await delay(delaySeconds * 1000 + 1000);
@@ -121,35 +151,27 @@ describe('Flow - Approve', () => {
       safeData.ctx.accounts.safe,
       ownerD.publicKey
     );
-    const sampleFlowC = await createSampleFlow(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      ixsC
-    );
-    const sampleFlowD = await createSampleFlow(
-      Keypair.generate(),
-      safeData.ctx.accounts.safe,
-      ixsD
-    );
+    const sampleFlowC = await createSampleFlow(safeData.ctx.accounts.safe, ixsC);
+    const sampleFlowD = await createSampleFlow(safeData.ctx.accounts.safe, ixsD);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlowC.flowKeypair.publicKey,
+      sampleFlowC.flowAddress,
       true
     );
     await approveData.builder.rpc();
     await program.methods
-      .executeMultisigFlow()
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
       .accounts(sampleFlowC.executeData.ctx.accounts)
       .remainingAccounts(sampleFlowC.executeData.ctx.remainingAccounts)
       .rpc();
 
     try {
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        sampleFlowD.flowKeypair.publicKey,
+        sampleFlowD.flowAddress,
         true
       );
       await approveData.builder.rpc();
@@ -158,4 +180,292 @@ describe('Flow - Approve', () => {
       assert.strictEqual(error.error.errorCode.code, 'ConstraintRaw');
     }
   });
+
+  describe('Rebase Flow', () => {
+    it('Keeps approvals from current owners only', async () => {
+      const rebaseSafeData = await createSampleSafe(owners, 2);
+      const safeAddress = rebaseSafeData.ctx.accounts.safe;
+      const pendingFlow = await createSampleFlow(safeAddress, []);
+      await (
+        await safeService.approveProposal(
+          ownerB.publicKey,
+          safeAddress,
+          pendingFlow.flowAddress,
+          true
+        )
+      ).builder.signers([ownerB]).rpc();
+
+      const ixs = await safeService.createSwapOwnerInstruction(
+        safeAddress,
+        ownerB.publicKey,
+        ownerC.publicKey
+      );
+      const swapFlow = await createSampleFlow(safeAddress, ixs);
+      await (
+        await safeService.approveProposal(
+          anchorProvider.wallet.publicKey,
+          safeAddress,
+          swapFlow.flowAddress,
+          true
+        )
+      ).builder.rpc();
+      await (
+        await safeService.approveProposal(
+          ownerB.publicKey,
+          safeAddress,
+          swapFlow.flowAddress,
+          true
+        )
+      ).builder.signers([ownerB]).rpc();
+      await program.methods
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+        .accounts(swapFlow.executeData.ctx.accounts)
+        .remainingAccounts(swapFlow.executeData.ctx.remainingAccounts)
+        .rpc();
+
+      const rebaseData = safeService.rebaseFlow(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        pendingFlow.flowAddress
+      );
+      await rebaseData.builder.rpc();
+
+      const flowAccount = await program.account.flow.fetch(pendingFlow.flowAddress);
+      assert.strictEqual(flowAccount.ownerSetSeqno.toNumber(), 1);
+      assert.strictEqual((flowAccount.approvals as any).length, 0);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Pending);
+
+      try {
+        await rebaseData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'FlowIsUpToDate');
+      }
+    });
+
+    it('Rebased flow can be approved again', async () => {
+      const rebaseSafeData = await createSampleSafe(owners, 1);
+      const safeAddress = rebaseSafeData.ctx.accounts.safe;
+      const pendingFlow = await createSampleFlow(safeAddress, []);
+      await executeSampleFlow(
+        safeAddress,
+        await safeService.createAddOwnerInstruction(safeAddress, ownerC.publicKey)
+      );
+
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        pendingFlow.flowAddress,
+        true
+      );
+      try {
+        await approveData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'ConstraintRaw');
+      }
+
+      await safeService
+        .rebaseFlow(anchorProvider.wallet.publicKey, safeAddress, pendingFlow.flowAddress)
+        .builder.rpc();
+      await approveData.builder.rpc();
+
+      const flowAccount = await program.account.flow.fetch(pendingFlow.flowAddress);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
+    });
+  });
+
+  describe('Off-chain Approvals', () => {
+    it('Records a batch of signed approvals', async () => {
+      const batchSafeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey, ownerC.publicKey],
+        2
+      );
+      const safeAddress = batchSafeData.ctx.accounts.safe;
+      const sampleFlow = await createSampleFlow(safeAddress, []);
+
+      const approveData = await safeService.approveProposalWithSignatures(
+        safeAddress,
+        sampleFlow.flowAddress,
+        [ownerB, ownerC],
+        true
+      );
+      await approveData.builder.rpc();
+
+      const flowAccount = await program.account.flow.fetch(sampleFlow.flowAddress);
+      const approvals = flowAccount.approvals as any[];
+      assert.strictEqual(approvals.length, 2);
+      assert.ok(approvals[0].owner.equals(ownerB.publicKey));
+      assert.ok(approvals[1].owner.equals(ownerC.publicKey));
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
+    });
+
+    it('Refuses signatures from non-owners', async () => {
+      const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+
+      const approveData = await safeService.approveProposalWithSignatures(
+        safeData.ctx.accounts.safe,
+        sampleFlow.flowAddress,
+        [ownerD],
+        true
+      );
+      try {
+        await approveData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidOwner');
+      }
+    });
+
+    it('Requires at least one signature', async () => {
+      const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+
+      const approveData = await safeService.approveProposalWithSignatures(
+        safeData.ctx.accounts.safe,
+        sampleFlow.flowAddress,
+        [],
+        true
+      );
+      try {
+        await approveData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'MissingApprovalSignatures');
+      }
+    });
+  });
+
+  describe('Revoke Approval', () => {
+    it('Revoking an approval moves the flow back to pending', async () => {
+      const revokeSafeData = await createSampleSafe(owners, 1);
+      const safeAddress = revokeSafeData.ctx.accounts.safe;
+      const sampleFlow = await createSampleFlow(safeAddress, []);
+      const flowAddress = sampleFlow.flowAddress;
+
+      await (
+        await safeService.approveProposal(
+          anchorProvider.wallet.publicKey,
+          safeAddress,
+          flowAddress,
+          true
+        )
+      ).builder.rpc();
+      let flowAccount = await program.account.flow.fetch(flowAddress);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
+
+      const revokeData = await safeService.revokeApproval(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        flowAddress
+      );
+      await revokeData.builder.rpc();
+      flowAccount = await program.account.flow.fetch(flowAddress);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Pending);
+      assert.strictEqual((flowAccount.approvals as any).length, 0);
+
+      try {
+        await revokeData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'ApprovalNotFound');
+      }
+
+      await (
+        await safeService.approveProposal(
+          anchorProvider.wallet.publicKey,
+          safeAddress,
+          flowAddress,
+          false
+        )
+      ).builder.rpc();
+      flowAccount = await program.account.flow.fetch(flowAddress);
+      assert.strictEqual((flowAccount.approvals as any)[0].isApproved, false);
+    });
+
+    it('Cannot replay a signed approval after revoking it', async () => {
+      const revokeSafeData = await createSampleSafe(owners, 2);
+      const safeAddress = revokeSafeData.ctx.accounts.safe;
+      const sampleFlow = await createSampleFlow(safeAddress, []);
+      const flowAddress = sampleFlow.flowAddress;
+
+      const approveData = await safeService.approveProposalWithSignatures(
+        safeAddress,
+        flowAddress,
+        [ownerB],
+        true
+      );
+      await approveData.builder.rpc();
+      await (
+        await safeService.revokeApproval(ownerB.publicKey, safeAddress, flowAddress)
+      ).builder
+        .signers([ownerB])
+        .rpc();
+
+      let flowAccount = await program.account.flow.fetch(flowAddress);
+      assert.strictEqual(flowAccount.approvalNonce, 1);
+      assert.strictEqual((flowAccount.approvals as any).length, 0);
+
+      try {
+        await approveData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'MissingApprovalSignatures');
+      }
+      flowAccount = await program.account.flow.fetch(flowAddress);
+      assert.strictEqual((flowAccount.approvals as any).length, 0);
+    });
+
+    it('Cannot revoke after execution', async () => {
+      const revokeSafeData = await createSampleSafe(owners, 1);
+      const safeAddress = revokeSafeData.ctx.accounts.safe;
+      const executedFlow = await executeSampleFlow(safeAddress, []);
+
+      const revokeData = await safeService.revokeApproval(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        executedFlow.flowAddress
+      );
+      try {
+        await revokeData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'ApprovalCannotBeRevoked');
+      }
+    });
+  });
+
+  describe('Content Hash', () => {
+    it('Approval is bound to the flow content', async () => {
+      const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+      const flowAddress = sampleFlow.flowAddress;
+
+      const staleData = SafeInstructionService.approveProposalIxBase(
+        safeData.ctx.accounts.safe,
+        flowAddress,
+        anchorProvider.wallet.publicKey,
+        true,
+        new Array(32).fill(0)
+      );
+      try {
+        await program.methods
+          .approveProposal(staleData.isApproved, staleData.contentHash, staleData.ownerAddress)
+          .accounts(staleData.ctx.accounts)
+          .rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'FlowContentHashMismatch');
+      }
+
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      const flowAccount = await program.account.flow.fetch(flowAddress);
+      assert.deepEqual((flowAccount.approvals as any)[0].contentHash, approveData.contentHash);
+    });
+  });
 });
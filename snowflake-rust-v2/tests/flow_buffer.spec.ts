@@ -0,0 +1,127 @@
+import { Keypair, TransactionInstruction } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import SafeInstructionService from './services/safeInstructionService';
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  createSampleFlowDraft,
+  safeService,
+  ownerB,
+  SafeData,
+  EXECUTE_ALL_ACTIONS,
+} from './helper';
+
+describe('Flow - Instruction Buffer', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+  let safeData: SafeData;
+
+  before(async () => {
+    safeData = await createSampleSafe(owners, 1);
+  });
+
+  const createApprovedBufferedFlow = async (ix: TransactionInstruction, data: Buffer) => {
+    const safeAddress = safeData.ctx.accounts.safe;
+    const bufferAddress = await safeService.uploadInstructionBuffer(
+      anchorProvider.wallet.publicKey,
+      safeAddress,
+      data,
+      16
+    );
+    const { flowAddress } = await createSampleFlowDraft(safeAddress, []);
+    const editData = SafeInstructionService.editActionIxBase(
+      safeAddress,
+      flowAddress,
+      anchorProvider.wallet.publicKey
+    );
+    await program.methods
+      .addAction(SafeInstructionService.bufferedAction(ix, bufferAddress), true)
+      .accounts(editData.ctx.accounts)
+      .rpc();
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      safeAddress,
+      flowAddress,
+      true
+    );
+    await approveData.builder.rpc();
+
+    const [safeSignerAddress] = await safeService.findSafeSignerAddress(safeAddress);
+    const flowAccount = await program.account.flow.fetch(flowAddress);
+    const executeData = SafeInstructionService.executeMultisigFlowIxBase(
+      flowAddress,
+      safeAddress,
+      safeSignerAddress,
+      anchorProvider.wallet.publicKey,
+      flowAccount.actions
+    );
+
+    return { bufferAddress, flowAddress, executeData };
+  };
+
+  it('Can execute an action whose data is written to a buffer in chunks', async () => {
+    const newOwner = Keypair.generate().publicKey;
+    const [ix] = await safeService.createAddOwnerInstruction(safeData.ctx.accounts.safe, newOwner);
+    const { bufferAddress, executeData } = await createApprovedBufferedFlow(ix, ix.data);
+
+    const bufferAccount = await program.account.instructionBuffer.fetch(bufferAddress);
+    assert.ok(bufferAccount.sealed);
+    assert.ok(Buffer.from(bufferAccount.data).equals(ix.data));
+
+    await program.methods
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+      .accounts(executeData.ctx.accounts)
+      .remainingAccounts(executeData.ctx.remainingAccounts)
+      .rpc();
+
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.ok(safeAccount.owners.some(owner => owner.equals(newOwner)));
+  });
+
+  it('Cannot execute an action if the buffer data does not match', async () => {
+    const [ix] = await safeService.createAddOwnerInstruction(
+      safeData.ctx.accounts.safe,
+      Keypair.generate().publicKey
+    );
+    const [otherIx] = await safeService.createAddOwnerInstruction(
+      safeData.ctx.accounts.safe,
+      Keypair.generate().publicKey
+    );
+    const { executeData } = await createApprovedBufferedFlow(ix, otherIx.data);
+
+    try {
+      await program.methods
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+        .accounts(executeData.ctx.accounts)
+        .remainingAccounts(executeData.ctx.remainingAccounts)
+        .rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidInstructionBuffer');
+    }
+  });
+
+  it('Cannot write to a sealed buffer', async () => {
+    const bufferAddress = await safeService.uploadInstructionBuffer(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe,
+      Buffer.from([1, 2, 3])
+    );
+
+    try {
+      await safeService
+        .writeInstructionBuffer(anchorProvider.wallet.publicKey, bufferAddress, 0, Buffer.from([4]))
+        .builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InstructionBufferIsSealed');
+    }
+
+    await safeService
+      .closeInstructionBuffer(anchorProvider.wallet.publicKey, bufferAddress)
+      .builder.rpc();
+    const bufferAccount = await program.account.instructionBuffer.fetchNullable(bufferAddress);
+    assert.isNull(bufferAccount);
+  });
+});
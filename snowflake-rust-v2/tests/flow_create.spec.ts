@@ -1,4 +1,4 @@
-import { Keypair } from '@solana/web3.js';
+import { BN } from '@project-serum/anchor';
 import { assert } from 'chai';
 import { JobBuilder, RECURRING_FOREVER } from '@snowflake-so/snowflake-sdk';
 
@@ -7,6 +7,8 @@ import {
   program,
   anchorProvider,
   createSampleSafe,
+  createSampleFlow,
+  createSampleFlowDraft,
   safeService,
   ownerB,
   SafeData,
@@ -23,21 +25,18 @@ describe('Flow - Create', () => {
 
   it('Cannot create flow with invalid trigger type', async () => {
     const safeAddress = safeData.ctx.accounts.safe;
-    const keypair = Keypair.generate();
     const job = new JobBuilder().jobName('Add new owner').jobInstructions([]).build();
     job.triggerType = 5;
-    const flowData = safeService.createFlow(
+    const flowData = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       safeAddress,
-      job.toSerializableJob(),
-      keypair
+      job.toSerializableJob()
     );
 
     try {
       await program.methods
-        .createFlow(flowData.accountSize, flowData.serializableJob, !DRAFT_FLOW)
+        .createFlow(flowData.serializableJob, !DRAFT_FLOW)
         .accounts(flowData.ctx.accounts)
-        .signers([keypair])
         .rpc();
     } catch (error) {
       assert.strictEqual(error.error.errorCode.code, 'InvalidJobData');
@@ -46,21 +45,18 @@ describe('Flow - Create', () => {
 
   it('Cannot create flow with negative value of remaining_runs', async () => {
     const safeAddress = safeData.ctx.accounts.safe;
-    const keypair = Keypair.generate();
     const job = new JobBuilder().jobName('Add new owner').jobInstructions([]).build();
     job.remainingRuns = -1;
-    const flowData = safeService.createFlow(
+    const flowData = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       safeAddress,
-      job.toSerializableJob(),
-      keypair
+      job.toSerializableJob()
     );
 
     try {
       await program.methods
-        .createFlow(flowData.accountSize, flowData.serializableJob, !DRAFT_FLOW)
+        .createFlow(flowData.serializableJob, !DRAFT_FLOW)
         .accounts(flowData.ctx.accounts)
-        .signers([keypair])
         .rpc();
     } catch (error) {
       assert.strictEqual(error.error.errorCode.code, 'InvalidJobData');
@@ -69,22 +65,19 @@ describe('Flow - Create', () => {
 
   it('Cannot create flow with invalid RECURRING_FOREVER logic', async () => {
     const safeAddress = safeData.ctx.accounts.safe;
-    const keypair = Keypair.generate();
     const job = new JobBuilder().jobName('Add new owner').jobInstructions([]).build();
     job.recurring = false;
     job.remainingRuns = RECURRING_FOREVER;
-    const flowData = safeService.createFlow(
+    const flowData = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       safeAddress,
-      job.toSerializableJob(),
-      keypair
+      job.toSerializableJob()
     );
 
     try {
       await program.methods
-        .createFlow(flowData.accountSize, flowData.serializableJob, !DRAFT_FLOW)
+        .createFlow(flowData.serializableJob, !DRAFT_FLOW)
         .accounts(flowData.ctx.accounts)
-        .signers([keypair])
         .rpc();
     } catch (error) {
       assert.strictEqual(error.error.errorCode.code, 'InvalidJobData');
@@ -93,21 +86,18 @@ describe('Flow - Create', () => {
 
   it('Create program condition flow', async () => {
     const safeAddress = safeData.ctx.accounts.safe;
-    const keypair = Keypair.generate();
     const job = new JobBuilder().jobName('Add new owner').jobInstructions([]).build();
     job.triggerType = TriggerType.ProgramCondition;
     job.remainingRuns = 999;
-    const flowData = safeService.createFlow(
+    const flowData = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       safeAddress,
-      job.toSerializableJob(),
-      keypair
+      job.toSerializableJob()
     );
 
     await program.methods
-      .createFlow(flowData.accountSize, flowData.serializableJob, !DRAFT_FLOW)
+      .createFlow(flowData.serializableJob, !DRAFT_FLOW)
       .accounts(flowData.ctx.accounts)
-      .signers([keypair])
       .rpc();
 
     const flowAccount = await program.account.flow.fetch(flowData.ctx.accounts.flow);
@@ -117,22 +107,19 @@ describe('Flow - Create', () => {
 
   it('Create program condition flow with remainingRuns larger than maximum value', async () => {
     const safeAddress = safeData.ctx.accounts.safe;
-    const keypair = Keypair.generate();
     const job = new JobBuilder().jobName('Add new owner').jobInstructions([]).build();
     job.triggerType = TriggerType.ProgramCondition;
     job.remainingRuns = 31000;
-    const flowData = safeService.createFlow(
+    const flowData = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       safeAddress,
-      job.toSerializableJob(),
-      keypair
+      job.toSerializableJob()
     );
 
     try {
       await program.methods
-        .createFlow(flowData.accountSize, flowData.serializableJob, !DRAFT_FLOW)
+        .createFlow(flowData.serializableJob, !DRAFT_FLOW)
         .accounts(flowData.ctx.accounts)
-        .signers([keypair])
         .rpc();
     } catch (error) {
       assert.strictEqual(error.error.errorCode.code, 'InvalidRemainingRuns');
@@ -141,26 +128,107 @@ describe('Flow - Create', () => {
 
   it('Create program condition flow with RECURRING_FOREVER', async () => {
     const safeAddress = safeData.ctx.accounts.safe;
-    const keypair = Keypair.generate();
     const job = new JobBuilder().jobName('Add new owner').jobInstructions([]).build();
     job.triggerType = TriggerType.ProgramCondition;
     job.recurring = true;
     job.remainingRuns = RECURRING_FOREVER;
-    const flowData = safeService.createFlow(
+    const flowData = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       safeAddress,
-      job.toSerializableJob(),
-      keypair
+      job.toSerializableJob()
     );
 
     try {
       await program.methods
-        .createFlow(flowData.accountSize, flowData.serializableJob, !DRAFT_FLOW)
+        .createFlow(flowData.serializableJob, !DRAFT_FLOW)
         .accounts(flowData.ctx.accounts)
-        .signers([keypair])
         .rpc();
     } catch (error) {
       assert.strictEqual(error.error.errorCode.code, 'InvalidRemainingRuns');
     }
   });
+
+  it('Flows are numbered sequentially per safe', async () => {
+    const numberedSafeData = await createSampleSafe(owners, 1);
+    const safeAddress = numberedSafeData.ctx.accounts.safe;
+    const firstFlow = await createSampleFlow(safeAddress, []);
+    const secondFlow = await createSampleFlow(safeAddress, []);
+
+    const [expectedAddress] = await safeService.findFlowAddress(safeAddress, new BN(1));
+    assert.ok(secondFlow.flowAddress.equals(expectedAddress));
+
+    const safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.strictEqual(safeAccount.proposalCount.toNumber(), 2);
+
+    const flows = await safeService.fetchFlows(safeAddress);
+    assert.strictEqual(flows.length, 2);
+    assert.strictEqual((flows[0] as any).proposalIndex.toNumber(), 0);
+    assert.strictEqual((flows[1] as any).proposalIndex.toNumber(), 1);
+    const firstFlowAccount = await program.account.flow.fetch(firstFlow.flowAddress);
+    assert.strictEqual(firstFlowAccount.proposalIndex.toNumber(), 0);
+  });
+
+  describe('Update Flow', () => {
+    const buildUpdate = async (flowAddress, job) =>
+      safeService.updateFlow(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flowAddress,
+        job.toSerializableJob()
+      );
+
+    it('Can update the schedule of a draft flow', async () => {
+      const sampleFlowData = await createSampleFlowDraft(safeData.ctx.accounts.safe, []);
+      const job = new JobBuilder()
+        .jobName('Monthly payout')
+        .jobInstructions([])
+        .scheduleCron('0 10 1 * *', 3)
+        .build();
+      const updateData = await buildUpdate(sampleFlowData.flowAddress, job);
+
+      await program.methods
+        .updateFlow(updateData.serializableJob)
+        .accounts(updateData.ctx.accounts)
+        .rpc();
+
+      const flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
+      assert.strictEqual(flowAccount.name, 'Monthly payout');
+      assert.strictEqual(flowAccount.cron, '0 10 1 * *');
+      assert.strictEqual(flowAccount.recurring, true);
+      assert.strictEqual(flowAccount.remainingRuns, 3);
+    });
+
+    it('Cannot update a flow with an invalid utc offset', async () => {
+      const sampleFlowData = await createSampleFlowDraft(safeData.ctx.accounts.safe, []);
+      const job = new JobBuilder().jobName('Sample job').jobInstructions([]).build();
+      const updateData = await buildUpdate(sampleFlowData.flowAddress, job);
+      updateData.serializableJob.userUtcOffset = 24 * 60 * 60;
+
+      try {
+        await program.methods
+          .updateFlow(updateData.serializableJob)
+          .accounts(updateData.ctx.accounts)
+          .rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidUtcOffset');
+      }
+    });
+
+    it('Cannot update a flow that is no longer a draft', async () => {
+      const sampleFlowData = await createSampleFlow(safeData.ctx.accounts.safe, []);
+      const job = new JobBuilder().jobName('Renamed job').jobInstructions([]).build();
+      const updateData = await buildUpdate(sampleFlowData.flowAddress, job);
+
+      try {
+        await program.methods
+          .updateFlow(updateData.serializableJob)
+          .accounts(updateData.ctx.accounts)
+          .rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'FlowMustBeInDraftedBeforeUpdate');
+      }
+    });
+  });
 });
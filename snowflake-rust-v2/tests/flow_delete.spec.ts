@@ -1,4 +1,4 @@
-import { Keypair } from '@solana/web3.js';
+import { BN } from '@project-serum/anchor';
 import { assert } from 'chai';
 
 import SafeInstructionService from './services/safeInstructionService';
@@ -7,10 +7,15 @@ import {
   anchorProvider,
   createSampleSafe,
   createSampleFlow,
+  createAddOwnerJob,
+  createSampleFlowWithJobWithExpiryDate,
+  getClusterUnixTimestamp,
+  delay,
   safeService,
   ownerB,
   ownerD,
   SafeData,
+  EXECUTE_ALL_ACTIONS,
 } from './helper';
 
 describe('Flow - Delete', () => {
@@ -22,31 +27,33 @@ describe('Flow - Delete', () => {
   });
 
   it('Can delete a flow', async () => {
-    const sampleFlow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, []);
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
     try {
       const deleteData = SafeInstructionService.deleteFlowIxBase(
         anchorProvider.wallet.publicKey,
-        sampleFlow.flowKeypair.publicKey
+        sampleFlow.flowAddress,
+        safeData.ctx.accounts.safe
       );
 
       await program.methods.deleteFlow().accounts(deleteData.ctx.accounts).rpc();
-      await program.account.flow.fetch(sampleFlow.flowKeypair.publicKey);
+      await program.account.flow.fetch(sampleFlow.flowAddress);
     } catch (error) {
       assert.strictEqual(
         error.message,
-        `Account does not exist ${sampleFlow.flowKeypair.publicKey}`
+        `Account does not exist ${sampleFlow.flowAddress}`
       );
     }
   });
 
   it('The caller must be the requestor of the flow', async () => {
     try {
-      const sampleFlow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, []);
+      const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
 
       const deleteData = SafeInstructionService.deleteFlowIxBase(
         ownerB.publicKey,
-        sampleFlow.flowKeypair.publicKey
+        sampleFlow.flowAddress,
+        safeData.ctx.accounts.safe
       );
 
       await program.methods.deleteFlow().accounts(deleteData.ctx.accounts).signers([ownerB]).rpc();
@@ -62,18 +69,18 @@ describe('Flow - Delete', () => {
       ownerD.publicKey
     );
 
-    const sampleFlow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, ixs);
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
 
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       safeData.ctx.accounts.safe,
-      sampleFlow.flowKeypair.publicKey,
+      sampleFlow.flowAddress,
       true
     );
     await approveData.builder.rpc();
 
     await program.methods
-      .executeMultisigFlow()
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
       .accounts(sampleFlow.executeData.ctx.accounts)
       .remainingAccounts(sampleFlow.executeData.ctx.remainingAccounts)
       .rpc();
@@ -81,7 +88,8 @@ describe('Flow - Delete', () => {
     try {
       const deleteData = SafeInstructionService.deleteFlowIxBase(
         anchorProvider.wallet.publicKey,
-        sampleFlow.flowKeypair.publicKey
+        sampleFlow.flowAddress,
+        safeData.ctx.accounts.safe
       );
 
       await program.methods.deleteFlow().accounts(deleteData.ctx.accounts).rpc();
@@ -90,4 +98,45 @@ describe('Flow - Delete', () => {
       assert.strictEqual(error.error.errorCode.code, 'ConstraintRaw');
     }
   });
+
+  it('Any owner can delete an expired flow', async () => {
+    const job = await createAddOwnerJob(safeData.ctx.accounts.safe, ownerD.publicKey);
+    const now = await getClusterUnixTimestamp();
+    const delaySeconds = 3;
+    const sampleFlow = await createSampleFlowWithJobWithExpiryDate(
+      safeData.ctx.accounts.safe,
+      job,
+      new BN(now + delaySeconds)
+    );
+    const deleteData = SafeInstructionService.deleteExpiredFlowIxBase(
+      ownerB.publicKey,
+      anchorProvider.wallet.publicKey,
+      sampleFlow.flowAddress,
+      safeData.ctx.accounts.safe
+    );
+
+    try {
+      await program.methods
+        .deleteExpiredFlow()
+        .accounts(deleteData.ctx.accounts)
+        .signers([ownerB])
+        .rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'FlowIsNotExpiredYet');
+    }
+
+    const safeBefore = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    await delay(delaySeconds * 1000 + 1000);
+    await program.methods
+      .deleteExpiredFlow()
+      .accounts(deleteData.ctx.accounts)
+      .signers([ownerB])
+      .rpc();
+
+    const flowAccount = await program.account.flow.fetchNullable(sampleFlow.flowAddress);
+    assert.isNull(flowAccount);
+    const safeAfter = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.strictEqual(safeAfter.activeFlows, safeBefore.activeFlows - 1);
+  });
 });
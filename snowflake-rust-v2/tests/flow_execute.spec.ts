@@ -5,10 +5,12 @@ import { assert } from 'chai';
 import { JobBuilder } from '@snowflake-so/snowflake-sdk';
 
 import { ProposalStateType, TriggerType } from './models/flow';
+import SafeInstructionService from './services/safeInstructionService';
 import {
   program,
   anchorProvider,
   createSampleSafe,
+  createSampleFlow,
   createSampleFlowWithJob,
   createAddOwnerJob,
   safeService,
@@ -18,6 +20,7 @@ import {
   ownerD,
   SafeData,
   createSampleFlowWithJobWithExpiryDate,
+  EXECUTE_ALL_ACTIONS,
 } from './helper';
 
 describe('Flow - Execute', () => {
@@ -31,14 +34,13 @@ describe('Flow - Execute', () => {
   describe('Execute Flow', () => {
     it('Flow must be in approved status', async () => {
       const addOwnerFlow = await createSampleFlowWithJob(
-        Keypair.generate(),
         safeData.ctx.accounts.safe,
         await createAddOwnerJob(safeData.ctx.accounts.safe, ownerD.publicKey)
       );
 
       try {
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(addOwnerFlow.executeData.ctx.accounts)
           .remainingAccounts(addOwnerFlow.executeData.ctx.remainingAccounts)
           .rpc();
@@ -49,15 +51,14 @@ describe('Flow - Execute', () => {
 
     it('Caller must be an owner of the safe that the flow points to', async () => {
       const addOwnerFlow = await createSampleFlowWithJob(
-        anchor.web3.Keypair.generate(),
         safeData.ctx.accounts.safe,
         await createAddOwnerJob(safeData.ctx.accounts.safe, ownerD.publicKey)
       );
 
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        addOwnerFlow.flowKeypair.publicKey,
+        addOwnerFlow.flowAddress,
         true
       );
       await approveData.builder.rpc();
@@ -69,7 +70,7 @@ describe('Flow - Execute', () => {
           caller: nonOwner.publicKey,
         };
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(accounts)
           .remainingAccounts(addOwnerFlow.executeData.ctx.remainingAccounts)
           .signers([nonOwner])
@@ -84,16 +85,15 @@ describe('Flow - Execute', () => {
       const now = await getClusterUnixTimestamp();
       const delaySeconds = 3;
       const addOwnerFlow = await createSampleFlowWithJobWithExpiryDate(
-        anchor.web3.Keypair.generate(),
         safeData.ctx.accounts.safe,
         await createAddOwnerJob(safeData.ctx.accounts.safe, ownerD.publicKey),
         new BN(now + delaySeconds)
       );
 
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        addOwnerFlow.flowKeypair.publicKey,
+        addOwnerFlow.flowAddress,
         true
       );
       await approveData.builder.rpc();
@@ -101,7 +101,7 @@ describe('Flow - Execute', () => {
       try {
         await delay(delaySeconds * 1000 + 1000);
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(addOwnerFlow.executeData.ctx.accounts)
           .remainingAccounts(addOwnerFlow.executeData.ctx.remainingAccounts)
           .rpc();
@@ -110,6 +110,157 @@ describe('Flow - Execute', () => {
         assert.strictEqual(error.error.errorCode.code, 'JobIsExpired');
       }
     });
+
+    it('Can execute a flow in batches', async () => {
+      const batchSafeData = await createSampleSafe(owners, 1);
+      const safeAddress = batchSafeData.ctx.accounts.safe;
+      const newOwners = [...Array(3).keys()].map(() => Keypair.generate().publicKey);
+      const ixs = [];
+      for (const newOwner of newOwners) {
+        ixs.push(...(await safeService.createAddOwnerInstruction(safeAddress, newOwner)));
+      }
+      const sampleFlowData = await createSampleFlow(safeAddress, ixs);
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        sampleFlowData.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      const executeBatch = () =>
+        program.methods
+          .executeMultisigFlow(2)
+          .accounts(sampleFlowData.executeData.ctx.accounts)
+          .remainingAccounts(sampleFlowData.executeData.ctx.remainingAccounts)
+          .rpc();
+
+      await executeBatch();
+      let flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
+      let safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.ExecutionInProgress);
+      assert.strictEqual(flowAccount.executedActions, 2);
+      assert.strictEqual(safeAccount.owners.length, 4);
+      assert.strictEqual(safeAccount.activeFlows, 1);
+
+      await executeBatch();
+      flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
+      safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Complete);
+      assert.strictEqual(flowAccount.executedActions, 3);
+      assert.strictEqual(safeAccount.owners.length, 5);
+      assert.strictEqual(safeAccount.activeFlows, 0);
+
+      try {
+        await executeBatch();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'RequestIsNotApprovedYet');
+      }
+    });
+
+    it('Can finish a batched flow after it expires', async () => {
+      const batchSafeData = await createSampleSafe(owners, 1);
+      const safeAddress = batchSafeData.ctx.accounts.safe;
+      const ixs = [];
+      for (const newOwner of [Keypair.generate().publicKey, Keypair.generate().publicKey]) {
+        ixs.push(...(await safeService.createAddOwnerInstruction(safeAddress, newOwner)));
+      }
+      const job = new JobBuilder().jobName('Sample job').jobInstructions(ixs).build();
+      const now = await getClusterUnixTimestamp();
+      const delaySeconds = 3;
+      const sampleFlowData = await createSampleFlowWithJobWithExpiryDate(
+        safeAddress,
+        job,
+        new BN(now + delaySeconds)
+      );
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        sampleFlowData.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      const executeBatch = () =>
+        program.methods
+          .executeMultisigFlow(1)
+          .accounts(sampleFlowData.executeData.ctx.accounts)
+          .remainingAccounts(sampleFlowData.executeData.ctx.remainingAccounts)
+          .rpc();
+
+      await executeBatch();
+      await delay(delaySeconds * 1000 + 1000);
+      await executeBatch();
+
+      const flowAccount = await program.account.flow.fetch(sampleFlowData.flowAddress);
+      assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Complete);
+      assert.strictEqual(flowAccount.executedActions, 2);
+    });
+
+    it('Flow waits for its prerequisite flows to complete', async () => {
+      const safeAddress = safeData.ctx.accounts.safe;
+      const [safeSignerAddress] = await safeService.findSafeSignerAddress(safeAddress);
+      const firstFlow = await createSampleFlowWithJob(
+        safeAddress,
+        await createAddOwnerJob(safeAddress, Keypair.generate().publicKey)
+      );
+
+      const job = await createAddOwnerJob(safeAddress, Keypair.generate().publicKey);
+      const flowData = await safeService.createFlow(
+        anchorProvider.wallet.publicKey,
+        safeAddress,
+        job.toSerializableJob()
+      );
+      flowData.serializableJob.prerequisites = [firstFlow.flowAddress];
+      await program.methods
+        .createFlow(flowData.serializableJob, false)
+        .accounts(flowData.ctx.accounts)
+        .rpc();
+
+      for (const flowAddress of [firstFlow.flowAddress, flowData.flowAddress]) {
+        const approveData = await safeService.approveProposal(
+          anchorProvider.wallet.publicKey,
+          safeAddress,
+          flowAddress,
+          true
+        );
+        await approveData.builder.rpc();
+      }
+
+      const flowAccount = await program.account.flow.fetch(flowData.flowAddress);
+      const executeData = SafeInstructionService.executeMultisigFlowIxBase(
+        flowData.flowAddress,
+        safeAddress,
+        safeSignerAddress,
+        anchorProvider.wallet.publicKey,
+        flowAccount.actions,
+        flowAccount.prerequisites
+      );
+      const executeSecondFlow = () =>
+        program.methods
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+          .accounts(executeData.ctx.accounts)
+          .remainingAccounts(executeData.ctx.remainingAccounts)
+          .rpc();
+
+      try {
+        await executeSecondFlow();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'PrerequisiteFlowNotComplete');
+      }
+
+      await program.methods
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+        .accounts(firstFlow.executeData.ctx.accounts)
+        .remainingAccounts(firstFlow.executeData.ctx.remainingAccounts)
+        .rpc();
+      await executeSecondFlow();
+
+      const secondFlowAccount = await program.account.flow.fetch(flowData.flowAddress);
+      assert.strictEqual(secondFlowAccount.proposalStage, ProposalStateType.Complete);
+    });
   });
 
   describe('Execute Scheduled Flow', () => {
@@ -126,13 +277,9 @@ describe('Flow - Execute', () => {
         .scheduleCron('0 10 1 * *')
         .build();
 
-      const sendSolFlow = await createSampleFlowWithJob(
-        anchor.web3.Keypair.generate(),
-        safeData.ctx.accounts.safe,
-        job
-      );
+      const sendSolFlow = await createSampleFlowWithJob(safeData.ctx.accounts.safe, job);
 
-      let flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+      let flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
 
       assert.strictEqual(flowAccount.triggerType, TriggerType.Time);
       assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Pending);
@@ -148,22 +295,22 @@ describe('Flow - Execute', () => {
         assert.strictEqual(error.error.errorCode.code, 'RequestIsNotExecutedYet');
       }
 
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        sendSolFlow.flowKeypair.publicKey,
+        sendSolFlow.flowAddress,
         true
       );
       await approveData.builder.rpc();
-      flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+      flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
       assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
 
       await program.methods
-        .executeMultisigFlow()
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
         .accounts(sendSolFlow.executeData.ctx.accounts)
         .remainingAccounts(sendSolFlow.executeData.ctx.remainingAccounts)
         .rpc();
-      flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+      flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
       assert.strictEqual(flowAccount.proposalStage, ProposalStateType.ExecutionInProgress);
 
       try {
@@ -198,13 +345,9 @@ describe('Flow - Execute', () => {
         .scheduleCron('0 10 1 * *')
         .build();
 
-      const sendSolFlow = await createSampleFlowWithJob(
-        anchor.web3.Keypair.generate(),
-        safeData.ctx.accounts.safe,
-        job
-      );
+      const sendSolFlow = await createSampleFlowWithJob(safeData.ctx.accounts.safe, job);
 
-      let flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+      let flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
 
       assert.strictEqual(flowAccount.triggerType, TriggerType.Time);
       assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Pending);
@@ -220,22 +363,22 @@ describe('Flow - Execute', () => {
         assert.strictEqual(error.error.errorCode.code, 'RequestIsNotExecutedYet');
       }
 
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        sendSolFlow.flowKeypair.publicKey,
+        sendSolFlow.flowAddress,
         true
       );
       await approveData.builder.rpc();
-      flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+      flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
       assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
 
       await program.methods
-        .executeMultisigFlow()
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
         .accounts(sendSolFlow.executeData.ctx.accounts)
         .remainingAccounts(sendSolFlow.executeData.ctx.remainingAccounts)
         .rpc();
-      flowAccount = await program.account.flow.fetch(sendSolFlow.flowKeypair.publicKey);
+      flowAccount = await program.account.flow.fetch(sendSolFlow.flowAddress);
       assert.strictEqual(flowAccount.proposalStage, ProposalStateType.ExecutionInProgress);
 
       try {
@@ -17,6 +17,7 @@ export const ownerC = anchor.web3.Keypair.generate();
 export const ownerD = anchor.web3.Keypair.generate();
 export const EMPTY_EXPIRY_DATE = undefined;
 export const DRAFT_FLOW = true;
+export const EXECUTE_ALL_ACTIONS = 0;
 
 export const delay = (milliSeconds: number) => {
   return new Promise(resolve => {
@@ -26,8 +27,21 @@ export const delay = (milliSeconds: number) => {
   });
 };
 
-export const createSampleSafe = async (owners: anchor.web3.PublicKey[], threshold: number) => {
-  const safeData = await safeService.createSafe(anchorProvider.wallet.publicKey, owners, threshold);
+export const createSampleSafe = async (
+  owners: anchor.web3.PublicKey[],
+  threshold: number,
+  ownerWeights: number[] = [],
+  ownerRoles: number[] = [],
+  configChangeDelay = 0
+) => {
+  const safeData = await safeService.createSafe(
+    anchorProvider.wallet.publicKey,
+    owners,
+    threshold,
+    ownerWeights,
+    ownerRoles
+  );
+  safeData.safe.configChangeDelay = configChangeDelay;
 
   await program.methods
     .createSafe(safeData.safe)
@@ -39,21 +53,40 @@ export const createSampleSafe = async (owners: anchor.web3.PublicKey[], threshol
 };
 
 export const createSampleFlow = async (
-  keypair: anchor.web3.Keypair,
   safeAddress: anchor.web3.PublicKey,
   ixs: anchor.web3.TransactionInstruction[]
 ) => {
   const job = new JobBuilder().jobName('Sample job').jobInstructions(ixs).build();
-  return createSampleFlowWithJobBase(keypair, safeAddress, job, EMPTY_EXPIRY_DATE, false);
+  return createSampleFlowWithJobBase(safeAddress, job, EMPTY_EXPIRY_DATE, false);
 };
 
 export const createSampleFlowDraft = async (
-  keypair: anchor.web3.Keypair,
   safeAddress: anchor.web3.PublicKey,
   ixs: anchor.web3.TransactionInstruction[]
 ) => {
   const job = new JobBuilder().jobName('Sample job').jobInstructions(ixs).build();
-  return createSampleFlowWithJobBase(keypair, safeAddress, job, EMPTY_EXPIRY_DATE, true);
+  return createSampleFlowWithJobBase(safeAddress, job, EMPTY_EXPIRY_DATE, true);
+};
+
+export const executeSampleFlow = async (
+  safeAddress: anchor.web3.PublicKey,
+  ixs: anchor.web3.TransactionInstruction[]
+) => {
+  const flow = await createSampleFlow(safeAddress, ixs);
+  const approveData = await safeService.approveProposal(
+    anchorProvider.wallet.publicKey,
+    safeAddress,
+    flow.flowAddress,
+    true
+  );
+  await approveData.builder.rpc();
+  await program.methods
+    .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+    .accounts(flow.executeData.ctx.accounts)
+    .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+    .rpc();
+
+  return flow;
 };
 
 export const createAddOwnerJob = async (
@@ -65,24 +98,21 @@ export const createAddOwnerJob = async (
 };
 
 export const createSampleFlowWithJob = async (
-  keypair: anchor.web3.Keypair,
   safeAddress: anchor.web3.PublicKey,
   job: Job
 ) => {
-  return createSampleFlowWithJobBase(keypair, safeAddress, job, EMPTY_EXPIRY_DATE, false);
+  return createSampleFlowWithJobBase(safeAddress, job, EMPTY_EXPIRY_DATE, false);
 };
 
 export const createSampleFlowWithJobWithExpiryDate = async (
-  keypair: anchor.web3.Keypair,
   safeAddress: anchor.web3.PublicKey,
   job: Job,
   expiryDate: BN
 ) => {
-  return createSampleFlowWithJobBase(keypair, safeAddress, job, expiryDate, false);
+  return createSampleFlowWithJobBase(safeAddress, job, expiryDate, false);
 };
 
 export const createSampleFlowWithJobBase = async (
-  keypair: anchor.web3.Keypair,
   safeAddress: anchor.web3.PublicKey,
   job: Job,
   expiryDate: BN,
@@ -90,11 +120,10 @@ export const createSampleFlowWithJobBase = async (
 ) => {
   const [safeSignerAddress] = await safeService.findSafeSignerAddress(safeAddress);
 
-  const flowData = safeService.createFlow(
+  const flowData = await safeService.createFlow(
     anchorProvider.wallet.publicKey,
     safeAddress,
-    job.toSerializableJob(),
-    keypair
+    job.toSerializableJob()
   );
 
   if (expiryDate) {
@@ -102,21 +131,20 @@ export const createSampleFlowWithJobBase = async (
   }
 
   await program.methods
-    .createFlow(flowData.accountSize, flowData.serializableJob, isDraft)
+    .createFlow(flowData.serializableJob, isDraft)
     .accounts(flowData.ctx.accounts)
-    .signers([keypair])
     .rpc();
 
-  const flowAccount = await program.account.flow.fetch(keypair.publicKey);
+  const flowAccount = await program.account.flow.fetch(flowData.flowAddress);
   const executeData = SafeInstructionService.executeMultisigFlowIxBase(
-    keypair.publicKey,
+    flowData.flowAddress,
     safeAddress,
     safeSignerAddress,
     anchorProvider.wallet.publicKey,
     flowAccount.actions
   );
 
-  return { flowData, executeData, flowKeypair: keypair };
+  return { flowData, executeData, flowAddress: flowData.flowAddress };
 };
 
 export const getClusterUnixTimestamp = async () => {
@@ -4,4 +4,6 @@ export default interface ApprovalRecord {
   owner: PublicKey;
   date: number;
   isApproved: boolean;
+  delegate: PublicKey;
+  contentHash: number[];
 }
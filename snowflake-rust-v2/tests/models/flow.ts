@@ -22,6 +22,8 @@ export interface Flow {
   extra: string;
   safe: PublicKey;
   ownerSetSeq: number;
+  vaultIndex?: number;
+  prerequisites?: PublicKey[];
   approvals: ApprovalRecord[];
   proposalState: ProposalStateType;
 }
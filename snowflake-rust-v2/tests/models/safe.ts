@@ -17,8 +17,30 @@ export type SafeType = {
   safeNonce: number;
   safeSignerNonce: number;
   owners: PublicKey[];
+  ownerWeights: number[];
+  ownerRoles: number[];
+  configChangeDelay: number;
   approvalsRequired: number;
-  ownerSetSeqno: number;
+  ownerSetSeqno: BN;
+  maxOwners: number;
+  guardians: PublicKey[];
+  guardianThreshold: number;
+  recoveryDelay: number;
+  frozen: boolean;
+  pauser: PublicKey;
+  vaultBumps: number[];
+  activeFlows: number;
+  proposalCount: BN;
   creator: PublicKey;
   createdAt: BN;
 };
+
+export enum OwnerRole {
+  Propose = 1,
+  Approve = 2,
+  Execute = 4,
+  Abort = 8,
+  ManageConfig = 16,
+}
+
+export const ALL_OWNER_ROLES = 0b11111;
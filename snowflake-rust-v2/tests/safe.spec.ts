@@ -1,16 +1,23 @@
-import { Keypair } from '@solana/web3.js';
+import { BN } from '@project-serum/anchor';
+import { Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
 import { assert } from 'chai';
 
+import { ALL_OWNER_ROLES, OwnerRole } from './models/safe';
 import {
   program,
   anchorProvider,
+  delay,
   createSampleSafe,
   createSampleFlow,
   createSampleFlowWithJob,
+  executeSampleFlow,
   createAddOwnerJob,
   safeService,
   ownerB,
   ownerC,
+  ownerD,
+  SafeData,
+  EXECUTE_ALL_ACTIONS,
 } from './helper';
 
 describe('Safe', () => {
@@ -22,8 +29,62 @@ describe('Safe', () => {
       const safeAccount = await program.account.safe.fetch(safeKeypair.publicKey);
 
       assert.deepEqual(safeAccount.owners, owners);
+      assert.deepEqual(safeAccount.ownerWeights, [1, 1]);
       assert.strictEqual(safeAccount.approvalsRequired, 1);
-      assert.strictEqual(safeAccount.ownerSetSeqno, 0);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 0);
+    });
+
+    it('Can create a weighted safe', async () => {
+      const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+
+      const { safeKeypair } = await createSampleSafe(owners, 4, [3, 1]);
+      const safeAccount = await program.account.safe.fetch(safeKeypair.publicKey);
+
+      assert.deepEqual(safeAccount.ownerWeights, [3, 1]);
+      assert.strictEqual(safeAccount.approvalsRequired, 4);
+    });
+
+    it('Owner weights must match owners', async () => {
+      const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+
+      try {
+        await createSampleSafe(owners, 1, [3]);
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidOwnerWeight');
+      }
+
+      try {
+        await createSampleSafe(owners, 1, [3, 0]);
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidOwnerWeight');
+      }
+    });
+
+    it('Can create a safe at a deterministic address', async () => {
+      const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+      const nonce = new BN(Date.now());
+      const [expectedSafeAddress] = await safeService.findSafeAddress(
+        anchorProvider.wallet.publicKey,
+        nonce
+      );
+
+      const safeData = await safeService.createSafeWithSeed(
+        anchorProvider.wallet.publicKey,
+        nonce,
+        owners,
+        1
+      );
+      await program.methods
+        .createSafeWithSeed(nonce, safeData.safe)
+        .accounts(safeData.ctx.accounts)
+        .rpc();
+
+      assert.ok(safeData.ctx.accounts.safe.equals(expectedSafeAddress));
+      const safeAccount = await program.account.safe.fetch(expectedSafeAddress);
+      assert.deepEqual(safeAccount.owners, owners);
+      assert.ok(safeAccount.creator.equals(anchorProvider.wallet.publicKey));
     });
 
     it('Invalid creator', async () => {
@@ -72,6 +133,14 @@ describe('Safe', () => {
       } catch (error) {
         assert.strictEqual(error.error.errorCode.code, 'InvalidMaxApprovalsRequired');
       }
+
+      try {
+        const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+        await createSampleSafe(owners, 5, [3, 1]);
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidMaxApprovalsRequired');
+      }
     });
   });
 
@@ -82,23 +151,19 @@ describe('Safe', () => {
         1
       );
       const job = await createAddOwnerJob(safeData.ctx.accounts.safe, ownerB.publicKey);
-      const flow = await createSampleFlowWithJob(
-        Keypair.generate(),
-        safeData.ctx.accounts.safe,
-        job
-      );
+      const flow = await createSampleFlowWithJob(safeData.ctx.accounts.safe, job);
 
-      const approveData = safeService.approveProposal(
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        flow.flowKeypair.publicKey,
+        flow.flowAddress,
         true
       );
       await approveData.builder.rpc();
 
       try {
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(flow.executeData.ctx.accounts)
           .remainingAccounts(flow.executeData.ctx.remainingAccounts)
           .rpc();
@@ -118,7 +183,7 @@ describe('Safe', () => {
 
       try {
         const result = await program.methods
-          .addOwner(ownerC.publicKey)
+          .addOwner(ownerC.publicKey, 1)
           .accounts({
             safe: safeData.safeKeypair.publicKey,
             safeSigner: safeSigner,
@@ -141,28 +206,28 @@ describe('Safe', () => {
         safeData.ctx.accounts.safe,
         ownerB.publicKey
       );
-      const flow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, ixs);
-      const approveData = safeService.approveProposal(
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        flow.flowKeypair.publicKey,
+        flow.flowAddress,
         true
       );
       await approveData.builder.rpc();
 
       let safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
       assert.strictEqual(safeAccount.owners.length, 2);
-      assert.strictEqual(safeAccount.ownerSetSeqno, 0);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 0);
 
       await program.methods
-        .executeMultisigFlow()
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
         .accounts(flow.executeData.ctx.accounts)
         .remainingAccounts(flow.executeData.ctx.remainingAccounts)
         .rpc();
 
       safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
       assert.strictEqual(safeAccount.owners.length, 1);
-      assert.strictEqual(safeAccount.ownerSetSeqno, 1);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
     });
 
     it('Owners cannot be empty', async () => {
@@ -171,21 +236,21 @@ describe('Safe', () => {
         safeData.ctx.accounts.safe,
         anchorProvider.wallet.publicKey
       );
-      const flow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, ixs);
-      const approveData = safeService.approveProposal(
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        flow.flowKeypair.publicKey,
+        flow.flowAddress,
         true
       );
       await approveData.builder.rpc();
 
       const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
       assert.strictEqual(safeAccount.owners.length, 1);
-      assert.strictEqual(safeAccount.ownerSetSeqno, 0);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 0);
       try {
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(flow.executeData.ctx.accounts)
           .remainingAccounts(flow.executeData.ctx.remainingAccounts)
           .rpc();
@@ -225,28 +290,28 @@ describe('Safe', () => {
         1
       );
       const ixs = await safeService.createChangeThresholdInstruction(safeData.ctx.accounts.safe, 2);
-      const flow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, ixs);
-      const approveData = safeService.approveProposal(
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        flow.flowKeypair.publicKey,
+        flow.flowAddress,
         true
       );
       await approveData.builder.rpc();
 
       let safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
       assert.strictEqual(safeAccount.approvalsRequired, 1);
-      assert.strictEqual(safeAccount.ownerSetSeqno, 0);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 0);
 
       await program.methods
-        .executeMultisigFlow()
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
         .accounts(flow.executeData.ctx.accounts)
         .remainingAccounts(flow.executeData.ctx.remainingAccounts)
         .rpc();
 
       safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
       assert.strictEqual(safeAccount.approvalsRequired, 2);
-      assert.strictEqual(safeAccount.ownerSetSeqno, 1);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
     });
 
     it('Threshold cannot be zero', async () => {
@@ -255,18 +320,18 @@ describe('Safe', () => {
         1
       );
       const ixs = await safeService.createChangeThresholdInstruction(safeData.ctx.accounts.safe, 0);
-      const flow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, ixs);
-      const approveData = safeService.approveProposal(
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        flow.flowKeypair.publicKey,
+        flow.flowAddress,
         true
       );
       await approveData.builder.rpc();
 
       try {
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(flow.executeData.ctx.accounts)
           .remainingAccounts(flow.executeData.ctx.remainingAccounts)
           .rpc();
@@ -282,18 +347,18 @@ describe('Safe', () => {
         safeData.ctx.accounts.safe,
         owners.length + 1
       );
-      const flow = await createSampleFlow(Keypair.generate(), safeData.ctx.accounts.safe, ixs);
-      const approveData = safeService.approveProposal(
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
         anchorProvider.wallet.publicKey,
         safeData.ctx.accounts.safe,
-        flow.flowKeypair.publicKey,
+        flow.flowAddress,
         true
       );
       await approveData.builder.rpc();
 
       try {
         await program.methods
-          .executeMultisigFlow()
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
           .accounts(flow.executeData.ctx.accounts)
           .remainingAccounts(flow.executeData.ctx.remainingAccounts)
           .rpc();
@@ -302,4 +367,542 @@ describe('Safe', () => {
       }
     });
   });
+
+  describe('Replace owners', () => {
+    it('Can replace the owner set atomically', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1
+      );
+      const newOwners = [anchorProvider.wallet.publicKey, ownerC.publicKey, ownerD.publicKey];
+      const ixs = await safeService.createSetOwnersInstruction(
+        safeData.ctx.accounts.safe,
+        newOwners,
+        2
+      );
+      await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.deepEqual(safeAccount.owners, newOwners);
+      assert.strictEqual(safeAccount.approvalsRequired, 2);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
+    });
+
+    it('Replaced owners must be unique', async () => {
+      const safeData = await createSampleSafe([anchorProvider.wallet.publicKey], 1);
+      const ixs = await safeService.createSetOwnersInstruction(
+        safeData.ctx.accounts.safe,
+        [ownerB.publicKey, ownerB.publicKey],
+        1
+      );
+
+      try {
+        await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'DuplicateOwnerInSafe');
+      }
+    });
+
+    it('Replaced owners keep the given weights and roles', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1
+      );
+      const newOwners = [anchorProvider.wallet.publicKey, ownerC.publicKey];
+      const ownerRoles = [ALL_OWNER_ROLES, OwnerRole.Propose];
+      const ixs = await safeService.createSetOwnersInstruction(
+        safeData.ctx.accounts.safe,
+        newOwners,
+        3,
+        [3, 1],
+        ownerRoles
+      );
+      await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.deepEqual(safeAccount.ownerWeights, [3, 1]);
+      assert.deepEqual(safeAccount.ownerRoles, ownerRoles);
+      assert.strictEqual(safeAccount.approvalsRequired, 3);
+    });
+
+    it('Replaced approvals cannot exceed the approver weight', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1
+      );
+      const ixs = await safeService.createSetOwnersInstruction(
+        safeData.ctx.accounts.safe,
+        [anchorProvider.wallet.publicKey, ownerC.publicKey],
+        2,
+        [],
+        [ALL_OWNER_ROLES, OwnerRole.Propose]
+      );
+
+      try {
+        await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidMaxApprovalsRequired');
+      }
+    });
+
+    it('Owner replacement waits for the config change delay', async () => {
+      const configChangeDelay = 3;
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [],
+        configChangeDelay
+      );
+      const safeAddress = safeData.ctx.accounts.safe;
+      const change = {
+        owners: [anchorProvider.wallet.publicKey, ownerC.publicKey],
+        ownerWeights: Buffer.from([1, 1]),
+        ownerRoles: Buffer.from([ALL_OWNER_ROLES, ALL_OWNER_ROLES]),
+        approvalsRequired: 2,
+      };
+      const setOwnersIxs = await safeService.createSetOwnersInstruction(
+        safeAddress,
+        change.owners,
+        change.approvalsRequired
+      );
+      const swapOwnerIxs = await safeService.createSwapOwnerInstruction(
+        safeAddress,
+        ownerB.publicKey,
+        ownerD.publicKey
+      );
+      await executeSampleFlow(safeAddress, [...setOwnersIxs, ...swapOwnerIxs]);
+
+      let safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.deepEqual(safeAccount.owners, [anchorProvider.wallet.publicKey, ownerB.publicKey]);
+      assert.strictEqual(safeAccount.pendingConfigChanges.length, 2);
+
+      await delay(configChangeDelay * 1000 + 1000);
+      await safeService
+        .applyConfigChange(
+          anchorProvider.wallet.publicKey,
+          safeAddress,
+          1,
+          ownerD.publicKey.toBuffer()
+        )
+        .builder.rpc();
+      safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.deepEqual(safeAccount.owners, [anchorProvider.wallet.publicKey, ownerD.publicKey]);
+
+      const payload = safeService.encodeConfigChangePayload('OwnerSetChange', change);
+      await safeService
+        .applyConfigChange(anchorProvider.wallet.publicKey, safeAddress, 0, payload)
+        .builder.rpc();
+      safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.deepEqual(safeAccount.owners, change.owners);
+      assert.strictEqual(safeAccount.approvalsRequired, 2);
+    });
+
+    it('Can swap an owner', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [2, 1]
+      );
+      const ixs = await safeService.createSwapOwnerInstruction(
+        safeData.ctx.accounts.safe,
+        ownerB.publicKey,
+        ownerC.publicKey
+      );
+      await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.deepEqual(safeAccount.owners, [anchorProvider.wallet.publicKey, ownerC.publicKey]);
+      assert.deepEqual(safeAccount.ownerWeights, [2, 1]);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
+    });
+  });
+
+  describe('Owner roles', () => {
+    it('Owner must have the approve role to approve', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [ALL_OWNER_ROLES, OwnerRole.Propose]
+      );
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+      const approveData = await safeService.approveProposal(
+        ownerB.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress,
+        true
+      );
+
+      try {
+        await approveData.builder.signers([ownerB]).rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'OwnerRoleRequired');
+      }
+    });
+
+    it('Can set owner roles', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1
+      );
+      const ixs = await safeService.createSetOwnerRolesInstruction(
+        safeData.ctx.accounts.safe,
+        ownerB.publicKey,
+        OwnerRole.Propose | OwnerRole.Approve
+      );
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      await program.methods
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+        .accounts(flow.executeData.ctx.accounts)
+        .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+        .rpc();
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.deepEqual(safeAccount.ownerRoles, [
+        ALL_OWNER_ROLES,
+        OwnerRole.Propose | OwnerRole.Approve,
+      ]);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
+    });
+
+    it('Owner must have the manage config role to execute a config change', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [ALL_OWNER_ROLES, OwnerRole.Propose | OwnerRole.Approve | OwnerRole.Execute]
+      );
+      const ixs = await safeService.createChangeThresholdInstruction(
+        safeData.ctx.accounts.safe,
+        1
+      );
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      try {
+        await program.methods
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+          .accounts({ ...flow.executeData.ctx.accounts, caller: ownerB.publicKey })
+          .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+          .signers([ownerB])
+          .rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'OwnerRoleRequired');
+      }
+    });
+
+    it('At least one owner must be able to manage config', async () => {
+      try {
+        await createSampleSafe(
+          [anchorProvider.wallet.publicKey],
+          1,
+          [],
+          [OwnerRole.Propose | OwnerRole.Approve]
+        );
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidOwnerRoles');
+      }
+    });
+  });
+
+  describe('Timelocked config changes', () => {
+    const configChangeDelay = 3;
+
+    const executeChangeThreshold = async (safeData: SafeData, threshold: number) => {
+      const ixs = await safeService.createChangeThresholdInstruction(
+        safeData.ctx.accounts.safe,
+        threshold
+      );
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+      await program.methods
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+        .accounts(flow.executeData.ctx.accounts)
+        .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+        .rpc();
+    };
+
+    it('Config change is applied only after the delay', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [],
+        configChangeDelay
+      );
+      await executeChangeThreshold(safeData, 2);
+
+      let safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.strictEqual(safeAccount.approvalsRequired, 1);
+      assert.strictEqual(safeAccount.pendingConfigChanges.length, 1);
+
+      const applyData = safeService.applyConfigChange(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        0
+      );
+      try {
+        await applyData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'ConfigChangeIsNotEffectiveYet');
+      }
+
+      await delay(configChangeDelay * 1000 + 1000);
+      await applyData.builder.rpc();
+
+      safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.strictEqual(safeAccount.approvalsRequired, 2);
+      assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
+      assert.strictEqual(safeAccount.pendingConfigChanges.length, 0);
+    });
+
+    it('Owner can cancel a pending config change', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [],
+        configChangeDelay
+      );
+      await executeChangeThreshold(safeData, 2);
+
+      const cancelData = safeService.cancelConfigChange(
+        ownerB.publicKey,
+        safeData.ctx.accounts.safe,
+        0
+      );
+      await cancelData.builder.signers([ownerB]).rpc();
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.strictEqual(safeAccount.approvalsRequired, 1);
+      assert.strictEqual(safeAccount.pendingConfigChanges.length, 0);
+
+      try {
+        await safeService
+          .applyConfigChange(anchorProvider.wallet.publicKey, safeData.ctx.accounts.safe, 0)
+          .builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'ConfigChangeNotFound');
+      }
+    });
+
+    it('Cannot cancel a config change once it is effective', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [],
+        configChangeDelay
+      );
+      await executeChangeThreshold(safeData, 2);
+      await delay(configChangeDelay * 1000 + 1000);
+
+      const cancelData = safeService.cancelConfigChange(
+        ownerB.publicKey,
+        safeData.ctx.accounts.safe,
+        0
+      );
+      try {
+        await cancelData.builder.signers([ownerB]).rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'ConfigChangeIsAlreadyEffective');
+      }
+    });
+
+    it('Owner needs the abort role to cancel a config change', async () => {
+      const safeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [ALL_OWNER_ROLES, OwnerRole.Propose | OwnerRole.Approve],
+        configChangeDelay
+      );
+      await executeChangeThreshold(safeData, 2);
+
+      const cancelData = safeService.cancelConfigChange(
+        ownerB.publicKey,
+        safeData.ctx.accounts.safe,
+        0
+      );
+      try {
+        await cancelData.builder.signers([ownerB]).rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'OwnerRoleRequired');
+      }
+    });
+  });
+
+  describe('Resize Safe', () => {
+    let safeData: SafeData;
+
+    before(async () => {
+      safeData = await createSampleSafe([anchorProvider.wallet.publicKey, ownerB.publicKey], 1);
+      const [safeSigner] = await safeService.findSafeSignerAddress(safeData.ctx.accounts.safe);
+      const airdropSignature = await anchorProvider.connection.requestAirdrop(
+        safeSigner,
+        LAMPORTS_PER_SOL
+      );
+      await anchorProvider.connection.confirmTransaction(airdropSignature);
+    });
+
+    it('Can update extra after creation', async () => {
+      const extra = JSON.stringify({ name: 'Treasury', description: 'Main treasury safe' });
+      const ixs = await safeService.createSetExtraInstruction(safeData.ctx.accounts.safe, extra);
+      await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.strictEqual(safeAccount.extra, extra);
+    });
+
+    it('Can raise the owner cap', async () => {
+      const ixs = await safeService.createSetMaxOwnersInstruction(safeData.ctx.accounts.safe, 128);
+      await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+
+      const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+      assert.strictEqual(safeAccount.maxOwners, 128);
+    });
+
+    it('Owner cap cannot be lower than the number of owners', async () => {
+      const ixs = await safeService.createSetMaxOwnersInstruction(safeData.ctx.accounts.safe, 2);
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      try {
+        await program.methods
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+          .accounts(flow.executeData.ctx.accounts)
+          .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+          .rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'InvalidMaxOwnerCount');
+      }
+    });
+
+    it('Owner cap cannot grow the safe by more than the realloc limit at once', async () => {
+      const ixs = await safeService.createSetMaxOwnersInstruction(safeData.ctx.accounts.safe, 254);
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, ixs);
+      const approveData = await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress,
+        true
+      );
+      await approveData.builder.rpc();
+
+      try {
+        await program.methods
+          .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+          .accounts(flow.executeData.ctx.accounts)
+          .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+          .rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'SafeResizeTooLarge');
+      }
+    });
+
+    it('Owner cap changes wait for the config change delay', async () => {
+      const configChangeDelay = 3;
+      const timelockedSafeData = await createSampleSafe(
+        [anchorProvider.wallet.publicKey, ownerB.publicKey],
+        1,
+        [],
+        [],
+        configChangeDelay
+      );
+      const safeAddress = timelockedSafeData.ctx.accounts.safe;
+      const [safeSigner] = await safeService.findSafeSignerAddress(safeAddress);
+      const airdropSignature = await anchorProvider.connection.requestAirdrop(
+        safeSigner,
+        LAMPORTS_PER_SOL
+      );
+      await anchorProvider.connection.confirmTransaction(airdropSignature);
+
+      const ixs = await safeService.createSetMaxOwnersInstruction(safeAddress, 96);
+      await executeSampleFlow(safeAddress, ixs);
+
+      let safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.strictEqual(safeAccount.maxOwners, 64);
+      assert.strictEqual(safeAccount.pendingConfigChanges.length, 1);
+
+      await delay(configChangeDelay * 1000 + 1000);
+      await safeService
+        .applyConfigChange(anchorProvider.wallet.publicKey, safeAddress, 0)
+        .builder.rpc();
+
+      safeAccount = await program.account.safe.fetch(safeAddress);
+      assert.strictEqual(safeAccount.maxOwners, 96);
+    });
+  });
+
+  describe('Migrate', () => {
+    it('Cannot migrate an up-to-date safe', async () => {
+      const safeData = await createSampleSafe([anchorProvider.wallet.publicKey], 1);
+      const migrateData = safeService.migrateSafe(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe
+      );
+
+      try {
+        await migrateData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'AccountIsAlreadyMigrated');
+      }
+    });
+
+    it('Cannot migrate an up-to-date flow', async () => {
+      const safeData = await createSampleSafe([anchorProvider.wallet.publicKey], 1);
+      const flow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+      const migrateData = safeService.migrateFlow(
+        anchorProvider.wallet.publicKey,
+        safeData.ctx.accounts.safe,
+        flow.flowAddress
+      );
+
+      try {
+        await migrateData.builder.rpc();
+        assert.fail();
+      } catch (error) {
+        assert.strictEqual(error.error.errorCode.code, 'AccountIsAlreadyMigrated');
+      }
+    });
+  });
 });
@@ -0,0 +1,144 @@
+import { BN } from '@project-serum/anchor';
+import { LAMPORTS_PER_SOL, PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  executeSampleFlow,
+  safeService,
+  delay,
+  ownerB,
+  SafeData,
+} from './helper';
+
+describe('Safe - Allowance', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+  const NATIVE_SOL_MINT = PublicKey.default;
+  const ONE_DAY = 24 * 60 * 60;
+  let safeData: SafeData;
+
+  before(async () => {
+    safeData = await createSampleSafe(owners, 1);
+    const [safeSigner] = await safeService.findSafeSignerAddress(safeData.ctx.accounts.safe);
+    const airdropSignature = await anchorProvider.connection.requestAirdrop(
+      safeSigner,
+      LAMPORTS_PER_SOL
+    );
+    await anchorProvider.connection.confirmTransaction(airdropSignature);
+
+    const ixs = await safeService.createSetAllowanceInstruction(
+      safeData.ctx.accounts.safe,
+      ownerB.publicKey,
+      NATIVE_SOL_MINT,
+      new BN(1000),
+      ONE_DAY
+    );
+    await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+  });
+
+  it('Can set an allowance', async () => {
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+
+    assert.strictEqual(safeAccount.allowances.length, 1);
+    assert.ok(safeAccount.allowances[0].spender.equals(ownerB.publicKey));
+    assert.strictEqual(safeAccount.allowances[0].amount.toNumber(), 1000);
+    assert.strictEqual(safeAccount.allowances[0].period, ONE_DAY);
+  });
+
+  it('Spender can spend SOL within the allowance', async () => {
+    const recipient = anchorProvider.wallet.publicKey;
+    const spendData = await safeService.spendSolAllowance(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      recipient,
+      new BN(600)
+    );
+    await spendData.builder.signers([ownerB]).rpc();
+
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.strictEqual(safeAccount.allowances[0].spent.toNumber(), 600);
+  });
+
+  it('Spender cannot exceed the allowance', async () => {
+    const recipient = anchorProvider.wallet.publicKey;
+    const spendData = await safeService.spendSolAllowance(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      recipient,
+      new BN(500)
+    );
+
+    try {
+      await spendData.builder.signers([ownerB]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'AllowanceExceeded');
+    }
+  });
+
+  it('Caller must have an allowance', async () => {
+    const recipient = anchorProvider.wallet.publicKey;
+    const spendData = await safeService.spendSolAllowance(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe,
+      recipient,
+      new BN(1)
+    );
+
+    try {
+      await spendData.builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'AllowanceNotFound');
+    }
+  });
+
+  it('Allowance changes wait for the config change delay', async () => {
+    const configChangeDelay = 3;
+    const timelockedSafeData = await createSampleSafe(owners, 1, [], [], configChangeDelay);
+    const safeAddress = timelockedSafeData.ctx.accounts.safe;
+    const change = {
+      spender: ownerB.publicKey,
+      mint: NATIVE_SOL_MINT,
+      amount: new BN(1000),
+      period: ONE_DAY,
+    };
+    const ixs = await safeService.createSetAllowanceInstruction(
+      safeAddress,
+      change.spender,
+      change.mint,
+      change.amount,
+      change.period
+    );
+    await executeSampleFlow(safeAddress, ixs);
+
+    let safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.strictEqual(safeAccount.allowances.length, 0);
+    assert.strictEqual(safeAccount.pendingConfigChanges.length, 1);
+
+    await delay(configChangeDelay * 1000 + 1000);
+    const tamperedPayload = safeService.encodeConfigChangePayload('AllowanceChange', {
+      ...change,
+      amount: new BN(1000000),
+    });
+    try {
+      await safeService
+        .applyConfigChange(anchorProvider.wallet.publicKey, safeAddress, 0, tamperedPayload)
+        .builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'ConfigChangePayloadMismatch');
+    }
+
+    const payload = safeService.encodeConfigChangePayload('AllowanceChange', change);
+    await safeService
+      .applyConfigChange(anchorProvider.wallet.publicKey, safeAddress, 0, payload)
+      .builder.rpc();
+
+    safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.strictEqual(safeAccount.allowances.length, 1);
+    assert.strictEqual(safeAccount.allowances[0].amount.toNumber(), 1000);
+  });
+});
@@ -0,0 +1,149 @@
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import SafeInstructionService from './services/safeInstructionService';
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  createSampleFlow,
+  executeSampleFlow,
+  safeService,
+  ownerB,
+  EXECUTE_ALL_ACTIONS,
+} from './helper';
+
+describe('Safe - Close', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+
+  it('Can close a safe and sweep the safe signer', async () => {
+    const safeData = await createSampleSafe(owners, 1);
+    const safeAddress = safeData.ctx.accounts.safe;
+    const [safeSignerAddress] = await safeService.findSafeSignerAddress(safeAddress);
+    const destination = Keypair.generate().publicKey;
+
+    const airdropSignature = await anchorProvider.connection.requestAirdrop(
+      safeSignerAddress,
+      LAMPORTS_PER_SOL
+    );
+    await anchorProvider.connection.confirmTransaction(airdropSignature);
+    const safeRent = await anchorProvider.connection.getBalance(safeAddress);
+
+    const ixs = await safeService.createCloseSafeInstruction(safeAddress, destination, true);
+    await executeSampleFlow(safeAddress, ixs);
+
+    const safeAccount = await program.account.safe.fetchNullable(safeAddress);
+    assert.isNull(safeAccount);
+
+    const destinationBalance = await anchorProvider.connection.getBalance(destination);
+    assert.strictEqual(destinationBalance, safeRent + LAMPORTS_PER_SOL);
+  });
+
+  it('Closed safe cannot be revived later in the same transaction', async () => {
+    const safeData = await createSampleSafe(owners, 1);
+    const safeAddress = safeData.ctx.accounts.safe;
+    const safeRent = await anchorProvider.connection.getBalance(safeAddress);
+
+    const ixs = await safeService.createCloseSafeInstruction(
+      safeAddress,
+      anchorProvider.wallet.publicKey,
+      false
+    );
+    const flow = await createSampleFlow(safeAddress, ixs);
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      safeAddress,
+      flow.flowAddress,
+      true
+    );
+    await approveData.builder.rpc();
+    await program.methods
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+      .accounts(flow.executeData.ctx.accounts)
+      .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+      .postInstructions([
+        SystemProgram.transfer({
+          fromPubkey: anchorProvider.wallet.publicKey,
+          toPubkey: safeAddress,
+          lamports: safeRent,
+        }),
+      ])
+      .rpc();
+
+    const safeInfo = await anchorProvider.connection.getAccountInfo(safeAddress);
+    assert.deepEqual([...safeInfo.data.subarray(0, 8)], new Array(8).fill(255));
+  });
+
+  it('Cannot close a safe with pending flows', async () => {
+    const safeData = await createSampleSafe(owners, 1);
+    const safeAddress = safeData.ctx.accounts.safe;
+    await createSampleFlow(safeAddress, []);
+
+    let safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.strictEqual(safeAccount.activeFlows, 1);
+
+    const ixs = await safeService.createCloseSafeInstruction(
+      safeAddress,
+      anchorProvider.wallet.publicKey,
+      false
+    );
+    try {
+      await executeSampleFlow(safeAddress, ixs);
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'SafeHasActiveFlows');
+    }
+
+    safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.strictEqual(safeAccount.activeFlows, 2);
+  });
+
+  it('Cannot close a safe while a vault holds lamports unless it is swept', async () => {
+    const safeData = await createSampleSafe(owners, 1);
+    const safeAddress = safeData.ctx.accounts.safe;
+    const destination = Keypair.generate().publicKey;
+    await executeSampleFlow(safeAddress, await safeService.createAddVaultInstruction(safeAddress));
+
+    const [vaultAddress] = await safeService.findVaultSignerAddress(safeAddress, 1);
+    const airdropSignature = await anchorProvider.connection.requestAirdrop(
+      vaultAddress,
+      LAMPORTS_PER_SOL
+    );
+    await anchorProvider.connection.confirmTransaction(airdropSignature);
+
+    let ixs = await safeService.createCloseSafeInstruction(safeAddress, destination, false);
+    const flow = await createSampleFlow(safeAddress, ixs);
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      safeAddress,
+      flow.flowAddress,
+      true
+    );
+    await approveData.builder.rpc();
+    try {
+      await program.methods
+        .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+        .accounts(flow.executeData.ctx.accounts)
+        .remainingAccounts(flow.executeData.ctx.remainingAccounts)
+        .rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'SafeVaultIsNotEmpty');
+    }
+
+    const deleteData = SafeInstructionService.deleteFlowIxBase(
+      anchorProvider.wallet.publicKey,
+      flow.flowAddress,
+      safeAddress
+    );
+    await program.methods.deleteFlow().accounts(deleteData.ctx.accounts).rpc();
+
+    ixs = await safeService.createCloseSafeInstruction(safeAddress, destination, true);
+    await executeSampleFlow(safeAddress, ixs);
+
+    const safeAccount = await program.account.safe.fetchNullable(safeAddress);
+    assert.isNull(safeAccount);
+    const vaultBalance = await anchorProvider.connection.getBalance(vaultAddress);
+    assert.strictEqual(vaultBalance, 0);
+  });
+});
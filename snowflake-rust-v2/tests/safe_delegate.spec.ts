@@ -0,0 +1,227 @@
+import { Keypair, PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  createSampleFlow,
+  safeService,
+  ownerB,
+  getClusterUnixTimestamp,
+  delay,
+  SafeData,
+} from './helper';
+
+describe('Safe - Delegate', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+  let safeData: SafeData;
+  let hotKey: Keypair;
+
+  beforeEach(async () => {
+    safeData = await createSampleSafe(owners, 2);
+    hotKey = Keypair.generate();
+  });
+
+  it('Delegate approves on behalf of the owner', async () => {
+    const setDelegateData = await safeService.setDelegate(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      hotKey.publicKey
+    );
+    await setDelegateData.builder.signers([ownerB]).rpc();
+
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+    const approveData = await safeService.approveProposal(
+      hotKey.publicKey,
+      safeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      true,
+      ownerB.publicKey
+    );
+    await approveData.builder.signers([hotKey]).rpc();
+
+    const flowAccount = await program.account.flow.fetch(sampleFlow.flowAddress);
+    const approvals = flowAccount.approvals as any[];
+    const delegatedApproval = approvals.find((item) => item.owner.equals(ownerB.publicKey));
+    assert.ok(delegatedApproval.delegate.equals(hotKey.publicKey));
+
+    try {
+      await (
+        await safeService.approveProposal(
+          ownerB.publicKey,
+          safeData.ctx.accounts.safe,
+          sampleFlow.flowAddress,
+          true
+        )
+      ).builder.signers([ownerB]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'AddressSignedAlready');
+    }
+  });
+
+  it('Expired delegate cannot approve', async () => {
+    const now = await getClusterUnixTimestamp();
+    const setDelegateData = await safeService.setDelegate(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      hotKey.publicKey,
+      now + 2
+    );
+    await setDelegateData.builder.signers([ownerB]).rpc();
+    await delay(3000);
+
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+    const approveData = await safeService.approveProposal(
+      hotKey.publicKey,
+      safeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      true,
+      ownerB.publicKey
+    );
+    try {
+      await approveData.builder.signers([hotKey]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidDelegate');
+    }
+  });
+
+  it('Expired delegate cannot revoke the owner vote', async () => {
+    const now = await getClusterUnixTimestamp();
+    await (
+      await safeService.setDelegate(
+        ownerB.publicKey,
+        safeData.ctx.accounts.safe,
+        hotKey.publicKey,
+        now + 3
+      )
+    ).builder
+      .signers([ownerB])
+      .rpc();
+
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+    await (
+      await safeService.approveProposal(
+        hotKey.publicKey,
+        safeData.ctx.accounts.safe,
+        sampleFlow.flowAddress,
+        true,
+        ownerB.publicKey
+      )
+    ).builder
+      .signers([hotKey])
+      .rpc();
+    await delay(4000);
+
+    const revokeData = await safeService.revokeApproval(
+      hotKey.publicKey,
+      safeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      ownerB.publicKey
+    );
+    try {
+      await revokeData.builder.signers([hotKey]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidDelegate');
+    }
+
+    const flowAccount = await program.account.flow.fetch(sampleFlow.flowAddress);
+    assert.strictEqual((flowAccount.approvals as any).length, 1);
+  });
+
+  it('Delegate can only approve for the owner who delegated to them', async () => {
+    await (
+      await safeService.setDelegate(ownerB.publicKey, safeData.ctx.accounts.safe, hotKey.publicKey)
+    ).builder
+      .signers([ownerB])
+      .rpc();
+
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+    const approveData = await safeService.approveProposal(
+      hotKey.publicKey,
+      safeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      true,
+      anchorProvider.wallet.publicKey
+    );
+    try {
+      await approveData.builder.signers([hotKey]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidDelegate');
+    }
+  });
+
+  it('Revoked delegate cannot approve', async () => {
+    await (
+      await safeService.setDelegate(ownerB.publicKey, safeData.ctx.accounts.safe, hotKey.publicKey)
+    ).builder
+      .signers([ownerB])
+      .rpc();
+    await (await safeService.revokeDelegate(ownerB.publicKey, safeData.ctx.accounts.safe)).builder
+      .signers([ownerB])
+      .rpc();
+
+    const [delegateRecordAddress] = await safeService.findDelegateAddress(
+      safeData.ctx.accounts.safe,
+      ownerB.publicKey
+    );
+    const delegateRecord = await program.account.delegate.fetchNullable(delegateRecordAddress);
+    assert.strictEqual(delegateRecord, null);
+
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+    const approveData = await safeService.approveProposal(
+      hotKey.publicKey,
+      safeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      true,
+      ownerB.publicKey
+    );
+    try {
+      await approveData.builder.signers([hotKey]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidDelegate');
+    }
+
+    try {
+      await (
+        await safeService.revokeDelegate(ownerB.publicKey, safeData.ctx.accounts.safe)
+      ).builder
+        .signers([ownerB])
+        .rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'AccountNotInitialized');
+    }
+  });
+
+  it('Owner cannot delegate to themselves', async () => {
+    const setDelegateData = await safeService.setDelegate(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      ownerB.publicKey
+    );
+    try {
+      await setDelegateData.builder.signers([ownerB]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidDelegate');
+    }
+
+    const setDefaultData = await safeService.setDelegate(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      PublicKey.default
+    );
+    try {
+      await setDefaultData.builder.signers([ownerB]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidDelegate');
+    }
+  });
+});
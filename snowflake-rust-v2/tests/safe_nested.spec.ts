@@ -0,0 +1,77 @@
+import { assert } from 'chai';
+
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  createSampleFlow,
+  executeSampleFlow,
+  safeService,
+  ownerB,
+  SafeData,
+} from './helper';
+import { ProposalStateType } from './models/flow';
+
+describe('Safe - Nested', () => {
+  let parentSafeData: SafeData;
+  let childSafeData: SafeData;
+
+  beforeEach(async () => {
+    parentSafeData = await createSampleSafe([anchorProvider.wallet.publicKey], 1);
+    const [parentSignerAddress] = await safeService.findSafeSignerAddress(
+      parentSafeData.ctx.accounts.safe
+    );
+    childSafeData = await createSampleSafe(
+      [anchorProvider.wallet.publicKey, ownerB.publicKey, parentSignerAddress],
+      2
+    );
+  });
+
+  it('Parent safe approves a child flow', async () => {
+    const [parentSignerAddress] = await safeService.findSafeSignerAddress(
+      parentSafeData.ctx.accounts.safe
+    );
+    const childFlow = await createSampleFlow(childSafeData.ctx.accounts.safe, []);
+    await (
+      await safeService.approveProposal(
+        anchorProvider.wallet.publicKey,
+        childSafeData.ctx.accounts.safe,
+        childFlow.flowAddress,
+        true
+      )
+    ).builder.rpc();
+
+    const approveIxs = await safeService.createApproveProposalInstruction(
+      childSafeData.ctx.accounts.safe,
+      childFlow.flowAddress,
+      parentSignerAddress,
+      true
+    );
+    await executeSampleFlow(parentSafeData.ctx.accounts.safe, approveIxs);
+
+    const flowAccount = await program.account.flow.fetch(childFlow.flowAddress);
+    const approvals = flowAccount.approvals as any[];
+    assert.strictEqual(approvals.length, 2);
+    assert.ok(approvals[1].owner.equals(parentSignerAddress));
+    assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Approved);
+  });
+
+  it('Parent signer cannot approve outside of a parent flow', async () => {
+    const [parentSignerAddress] = await safeService.findSafeSignerAddress(
+      parentSafeData.ctx.accounts.safe
+    );
+    const childFlow = await createSampleFlow(childSafeData.ctx.accounts.safe, []);
+    const approveData = await safeService.approveProposal(
+      parentSignerAddress,
+      childSafeData.ctx.accounts.safe,
+      childFlow.flowAddress,
+      true
+    );
+    try {
+      await approveData.builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.match(error.message, /Signature verification failed|Missing signature/);
+    }
+  });
+});
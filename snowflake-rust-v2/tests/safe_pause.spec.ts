@@ -0,0 +1,123 @@
+import { PublicKey } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import {
+  program,
+  anchorProvider,
+  delay,
+  createSampleSafe,
+  createSampleFlow,
+  executeSampleFlow,
+  safeService,
+  ownerB,
+  SafeData,
+} from './helper';
+
+describe('Safe - Pause', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+  let safeData: SafeData;
+
+  beforeEach(async () => {
+    safeData = await createSampleSafe(owners, 1);
+    const setPauserIxs = await safeService.createSetPauserInstruction(
+      safeData.ctx.accounts.safe,
+      ownerB.publicKey
+    );
+    const changeThresholdIxs = await safeService.createChangeThresholdInstruction(
+      safeData.ctx.accounts.safe,
+      2
+    );
+    await executeSampleFlow(safeData.ctx.accounts.safe, [...setPauserIxs, ...changeThresholdIxs]);
+  });
+
+  it('Only the pauser can freeze the safe', async () => {
+    const pauseByOwnerData = safeService.pauseSafe(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe
+    );
+    try {
+      await pauseByOwnerData.builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidPauser');
+    }
+
+    const pauseData = safeService.pauseSafe(ownerB.publicKey, safeData.ctx.accounts.safe);
+    await pauseData.builder.signers([ownerB]).rpc();
+
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.ok(safeAccount.pauser.equals(ownerB.publicKey));
+    assert.strictEqual(safeAccount.frozen, true);
+  });
+
+  it('Frozen safe refuses approvals', async () => {
+    const sampleFlow = await createSampleFlow(safeData.ctx.accounts.safe, []);
+    const pauseData = safeService.pauseSafe(ownerB.publicKey, safeData.ctx.accounts.safe);
+    await pauseData.builder.signers([ownerB]).rpc();
+
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe,
+      sampleFlow.flowAddress,
+      true
+    );
+    try {
+      await approveData.builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'SafeIsFrozen');
+    }
+  });
+
+  it('Unfreezing requires the threshold', async () => {
+    const pauseData = safeService.pauseSafe(ownerB.publicKey, safeData.ctx.accounts.safe);
+    await pauseData.builder.signers([ownerB]).rpc();
+
+    const unpauseData = safeService.unpauseSafe(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe
+    );
+    await unpauseData.builder.rpc();
+
+    let safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.strictEqual(safeAccount.frozen, true);
+    assert.strictEqual((safeAccount.unfreezeApprovals as any).length, 1);
+
+    try {
+      await unpauseData.builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'AddressSignedAlready');
+    }
+
+    const unpauseByOwnerBData = safeService.unpauseSafe(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe
+    );
+    await unpauseByOwnerBData.builder.signers([ownerB]).rpc();
+
+    safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.strictEqual(safeAccount.frozen, false);
+    assert.strictEqual((safeAccount.unfreezeApprovals as any).length, 0);
+  });
+
+  it('Pauser changes wait for the config change delay', async () => {
+    const configChangeDelay = 3;
+    const timelockedSafeData = await createSampleSafe(owners, 1, [], [], configChangeDelay);
+    const safeAddress = timelockedSafeData.ctx.accounts.safe;
+    const ixs = await safeService.createSetPauserInstruction(safeAddress, ownerB.publicKey);
+    await executeSampleFlow(safeAddress, ixs);
+
+    let safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.ok(safeAccount.pauser.equals(PublicKey.default));
+    assert.strictEqual(safeAccount.pendingConfigChanges.length, 1);
+
+    await delay(configChangeDelay * 1000 + 1000);
+    await safeService
+      .applyConfigChange(anchorProvider.wallet.publicKey, safeAddress, 0)
+      .builder.rpc();
+
+    safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.ok(safeAccount.pauser.equals(ownerB.publicKey));
+  });
+});
@@ -0,0 +1,236 @@
+import { LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { assert } from 'chai';
+
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  executeSampleFlow,
+  safeService,
+  delay,
+  ownerB,
+  ownerC,
+  ownerD,
+  SafeData,
+} from './helper';
+
+describe('Safe - Recovery', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+  const recoveryDelay = 3;
+  let safeData: SafeData;
+
+  before(async () => {
+    const airdropSignature = await anchorProvider.connection.requestAirdrop(
+      ownerC.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    await anchorProvider.connection.confirmTransaction(airdropSignature);
+  });
+
+  beforeEach(async () => {
+    safeData = await createSampleSafe(owners, 1);
+    const ixs = await safeService.createSetGuardiansInstruction(
+      safeData.ctx.accounts.safe,
+      [ownerC.publicKey, ownerD.publicKey],
+      2,
+      recoveryDelay
+    );
+    await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+  });
+
+  it('Can set guardians', async () => {
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+
+    assert.deepEqual(safeAccount.guardians, [ownerC.publicKey, ownerD.publicKey]);
+    assert.strictEqual(safeAccount.guardianThreshold, 2);
+    assert.strictEqual(safeAccount.recoveryDelay, recoveryDelay);
+  });
+
+  it('Guardians can replace the owners after the delay', async () => {
+    const newOwners = [ownerC.publicKey, ownerD.publicKey];
+    const initiateData = await safeService.initiateRecovery(
+      ownerC.publicKey,
+      safeData.ctx.accounts.safe,
+      newOwners,
+      1
+    );
+    await initiateData.builder.signers([ownerC]).rpc();
+
+    const executeData = await safeService.executeRecovery(
+      ownerC.publicKey,
+      safeData.ctx.accounts.safe,
+      ownerC.publicKey
+    );
+    try {
+      await executeData.builder.signers([ownerC]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'RecoveryNotEnoughApprovals');
+    }
+
+    const approveData = await safeService.approveRecovery(
+      ownerD.publicKey,
+      safeData.ctx.accounts.safe
+    );
+    await approveData.builder.signers([ownerD]).rpc();
+
+    try {
+      await executeData.builder.signers([ownerC]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'RecoveryIsNotEffectiveYet');
+    }
+
+    await delay(recoveryDelay * 1000 + 1000);
+    await executeData.builder.signers([ownerC]).rpc();
+
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.deepEqual(safeAccount.owners, newOwners);
+    assert.strictEqual(safeAccount.approvalsRequired, 1);
+    assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 2);
+  });
+
+  it('Owner can cancel a recovery', async () => {
+    const initiateData = await safeService.initiateRecovery(
+      ownerC.publicKey,
+      safeData.ctx.accounts.safe,
+      [ownerC.publicKey],
+      1
+    );
+    await initiateData.builder.signers([ownerC]).rpc();
+
+    const cancelData = await safeService.cancelRecovery(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      ownerC.publicKey
+    );
+    await cancelData.builder.signers([ownerB]).rpc();
+
+    const recoveryAccount = await program.account.recovery.fetchNullable(
+      initiateData.recoveryAddress
+    );
+    assert.isNull(recoveryAccount);
+
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+    assert.deepEqual(safeAccount.owners, owners);
+  });
+
+  it('Owner cannot cancel a recovery once it is effective', async () => {
+    const initiateData = await safeService.initiateRecovery(
+      ownerC.publicKey,
+      safeData.ctx.accounts.safe,
+      [ownerC.publicKey],
+      1
+    );
+    await initiateData.builder.signers([ownerC]).rpc();
+    await delay(recoveryDelay * 1000 + 1000);
+
+    const cancelData = await safeService.cancelRecovery(
+      ownerB.publicKey,
+      safeData.ctx.accounts.safe,
+      ownerC.publicKey
+    );
+    try {
+      await cancelData.builder.signers([ownerB]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'RecoveryIsAlreadyEffective');
+    }
+  });
+
+  it('Guardian can clear an expired recovery', async () => {
+    const initiateData = await safeService.initiateRecovery(
+      ownerC.publicKey,
+      safeData.ctx.accounts.safe,
+      [ownerC.publicKey],
+      1
+    );
+    await initiateData.builder.signers([ownerC]).rpc();
+
+    const cancelData = await safeService.cancelRecovery(
+      ownerD.publicKey,
+      safeData.ctx.accounts.safe,
+      ownerC.publicKey
+    );
+    try {
+      await cancelData.builder.signers([ownerD]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'RecoveryIsNotExpiredYet');
+    }
+
+    await delay(recoveryDelay * 2000 + 1000);
+    const approveData = await safeService.approveRecovery(
+      ownerD.publicKey,
+      safeData.ctx.accounts.safe
+    );
+    try {
+      await approveData.builder.signers([ownerD]).rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'RecoveryIsExpired');
+    }
+
+    await cancelData.builder.signers([ownerD]).rpc();
+    const recoveryAccount = await program.account.recovery.fetchNullable(
+      initiateData.recoveryAddress
+    );
+    assert.isNull(recoveryAccount);
+
+    const reinitiateData = await safeService.initiateRecovery(
+      ownerC.publicKey,
+      safeData.ctx.accounts.safe,
+      [ownerC.publicKey, ownerD.publicKey],
+      1
+    );
+    await reinitiateData.builder.signers([ownerC]).rpc();
+  });
+
+  it('Guardian changes wait for the config change delay', async () => {
+    const configChangeDelay = 3;
+    const timelockedSafeData = await createSampleSafe(owners, 1, [], [], configChangeDelay);
+    const safeAddress = timelockedSafeData.ctx.accounts.safe;
+    const change = {
+      guardians: [ownerC.publicKey],
+      guardianThreshold: 1,
+      recoveryDelay,
+    };
+    const ixs = await safeService.createSetGuardiansInstruction(
+      safeAddress,
+      change.guardians,
+      change.guardianThreshold,
+      change.recoveryDelay
+    );
+    await executeSampleFlow(safeAddress, ixs);
+
+    let safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.deepEqual(safeAccount.guardians, []);
+    assert.strictEqual(safeAccount.pendingConfigChanges.length, 1);
+
+    await delay(configChangeDelay * 1000 + 1000);
+    const payload = safeService.encodeConfigChangePayload('GuardiansChange', change);
+    await safeService
+      .applyConfigChange(anchorProvider.wallet.publicKey, safeAddress, 0, payload)
+      .builder.rpc();
+
+    safeAccount = await program.account.safe.fetch(safeAddress);
+    assert.deepEqual(safeAccount.guardians, [ownerC.publicKey]);
+    assert.strictEqual(safeAccount.guardianThreshold, 1);
+  });
+
+  it('Only guardians can initiate a recovery', async () => {
+    const initiateData = await safeService.initiateRecovery(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe,
+      [anchorProvider.wallet.publicKey],
+      1
+    );
+
+    try {
+      await initiateData.builder.rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidGuardian');
+    }
+  });
+});
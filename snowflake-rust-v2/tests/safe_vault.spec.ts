@@ -0,0 +1,108 @@
+import { Keypair, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { assert } from 'chai';
+import { JobBuilder } from '@snowflake-so/snowflake-sdk';
+
+import SafeInstructionService from './services/safeInstructionService';
+import {
+  program,
+  anchorProvider,
+  createSampleSafe,
+  executeSampleFlow,
+  safeService,
+  ownerB,
+  SafeData,
+  EXECUTE_ALL_ACTIONS,
+} from './helper';
+
+describe('Safe - Vaults', () => {
+  const owners = [anchorProvider.wallet.publicKey, ownerB.publicKey];
+  let safeData: SafeData;
+
+  before(async () => {
+    safeData = await createSampleSafe(owners, 1);
+    const ixs = await safeService.createAddVaultInstruction(safeData.ctx.accounts.safe);
+    await executeSampleFlow(safeData.ctx.accounts.safe, ixs);
+  });
+
+  it('Can add a vault', async () => {
+    const [, vaultBump] = await safeService.findVaultSignerAddress(safeData.ctx.accounts.safe, 1);
+    const safeAccount = await program.account.safe.fetch(safeData.ctx.accounts.safe);
+
+    assert.deepEqual(safeAccount.vaultBumps, [vaultBump]);
+  });
+
+  it('Flow is signed by the selected vault', async () => {
+    const safeAddress = safeData.ctx.accounts.safe;
+    const [vaultAddress] = await safeService.findVaultSignerAddress(safeAddress, 1);
+    const [safeSignerAddress] = await safeService.findSafeSignerAddress(safeAddress);
+    const recipient = Keypair.generate().publicKey;
+
+    const airdropSignature = await anchorProvider.connection.requestAirdrop(
+      vaultAddress,
+      LAMPORTS_PER_SOL
+    );
+    await anchorProvider.connection.confirmTransaction(airdropSignature);
+
+    const ix = SystemProgram.transfer({
+      fromPubkey: vaultAddress,
+      toPubkey: recipient,
+      lamports: LAMPORTS_PER_SOL / 2,
+    });
+    const job = new JobBuilder().jobName('Pay from vault').jobInstructions([ix]).build();
+    const flowData = await safeService.createFlow(
+      anchorProvider.wallet.publicKey,
+      safeAddress,
+      { ...job.toSerializableJob(), vaultIndex: 1 }
+    );
+    await program.methods
+      .createFlow(flowData.serializableJob, false)
+      .accounts(flowData.ctx.accounts)
+      .rpc();
+
+    const approveData = await safeService.approveProposal(
+      anchorProvider.wallet.publicKey,
+      safeAddress,
+      flowData.flowAddress,
+      true
+    );
+    await approveData.builder.rpc();
+
+    const flowAccount = await program.account.flow.fetch(flowData.flowAddress);
+    assert.strictEqual(flowAccount.vaultIndex, 1);
+
+    const executeData = SafeInstructionService.executeMultisigFlowIxBase(
+      flowData.flowAddress,
+      safeAddress,
+      safeSignerAddress,
+      anchorProvider.wallet.publicKey,
+      flowAccount.actions
+    );
+    await program.methods
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
+      .accounts(executeData.ctx.accounts)
+      .remainingAccounts(executeData.ctx.remainingAccounts)
+      .rpc();
+
+    const recipientBalance = await anchorProvider.connection.getBalance(recipient);
+    assert.strictEqual(recipientBalance, LAMPORTS_PER_SOL / 2);
+  });
+
+  it('Cannot create a flow for an unknown vault', async () => {
+    const job = new JobBuilder().jobName('Unknown vault').jobInstructions([]).build();
+    const flowData = await safeService.createFlow(
+      anchorProvider.wallet.publicKey,
+      safeData.ctx.accounts.safe,
+      { ...job.toSerializableJob(), vaultIndex: 5 }
+    );
+
+    try {
+      await program.methods
+        .createFlow(flowData.serializableJob, false)
+        .accounts(flowData.ctx.accounts)
+        .rpc();
+      assert.fail();
+    } catch (error) {
+      assert.strictEqual(error.error.errorCode.code, 'InvalidVault');
+    }
+  });
+});
@@ -1,3 +1,4 @@
+import { createHash } from 'crypto';
 import { SHA256 } from 'crypto-js';
 import create from 'keccak';
 
@@ -9,4 +10,8 @@ export class HashService {
   static sha256(message: string): Buffer {
     return Buffer.from(SHA256(message).toString(), 'hex');
   }
+
+  static sha256Buffer(input: Buffer): Buffer {
+    return createHash('sha256').update(input).digest();
+  }
 }
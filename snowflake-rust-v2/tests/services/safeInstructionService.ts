@@ -1,24 +1,42 @@
 import { Program, ProgramAccount } from '@project-serum/anchor';
-import { JobBuilder } from '@snowflake-so/snowflake-sdk';
+import { JobBuilder, SerializableAction } from '@snowflake-so/snowflake-sdk';
 import {
   AccountMeta,
-  Keypair,
   PublicKey,
   TransactionInstruction,
   SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from '@solana/web3.js';
 import BN from 'bn.js';
 import { InstructionContextType } from '../models/anchor-context';
 import { Flow, TriggerType } from '../models/flow';
 import { Snowflake } from '../../target/types/snowflake';
+import { HashService } from './hashService';
 
 export type ClientSafeParams = {
   approvalsRequired: number;
   creator: PublicKey;
   createdAt: BN;
   signerBump: number;
+  ownerSetSeqno: BN;
+  maxOwners: number;
   extra: string;
   owners: PublicKey[];
+  ownerWeights: number[];
+  ownerRoles: number[];
+  configChangeDelay: number;
+  configChangeSeqno: number;
+  pendingConfigChanges: any[];
+  allowances: any[];
+  guardianThreshold: number;
+  recoveryDelay: number;
+  guardians: PublicKey[];
+  frozen: boolean;
+  pauser: PublicKey;
+  unfreezeApprovals: Buffer;
+  vaultBumps: number[];
+  activeFlows: number;
+  proposalCount: BN;
 };
 
 export default class SafeInstructionService {
@@ -45,8 +63,25 @@ export default class SafeInstructionService {
       creator: payerAddress,
       createdAt: new BN(0),
       signerBump: safeSignerNonce,
+      ownerSetSeqno: new BN(0),
+      maxOwners: 0,
       extra: '',
       owners: safeOwners.map<PublicKey>(owner => owner),
+      ownerWeights: [],
+      ownerRoles: [],
+      configChangeDelay: 0,
+      configChangeSeqno: 0,
+      pendingConfigChanges: [],
+      allowances: [],
+      guardianThreshold: 0,
+      recoveryDelay: 0,
+      guardians: [],
+      frozen: false,
+      pauser: PublicKey.default,
+      unfreezeApprovals: Buffer.alloc(0),
+      vaultBumps: [],
+      activeFlows: 0,
+      proposalCount: new BN(0),
     };
     const createSafeIx = await program.instruction.createSafe(safePath, safe, ctx);
     return createSafeIx;
@@ -58,7 +93,9 @@ export default class SafeInstructionService {
     safeSignerAddress: PublicKey,
     safeSignerNonce: number,
     safeOwners: PublicKey[],
-    approvalsRequired: number
+    approvalsRequired: number,
+    ownerWeights: number[] = [],
+    ownerRoles: number[] = []
   ) {
     const ctx = {
       accounts: {
@@ -74,8 +111,25 @@ export default class SafeInstructionService {
       creator: payerAddress,
       createdAt: new BN(0),
       signerBump: safeSignerNonce,
+      ownerSetSeqno: new BN(0),
+      maxOwners: 0,
       extra: '',
       owners: safeOwners.map<PublicKey>(owner => owner),
+      ownerWeights,
+      ownerRoles,
+      configChangeDelay: 0,
+      configChangeSeqno: 0,
+      pendingConfigChanges: [],
+      allowances: [],
+      guardianThreshold: 0,
+      recoveryDelay: 0,
+      guardians: [],
+      frozen: false,
+      pauser: PublicKey.default,
+      unfreezeApprovals: Buffer.alloc(0),
+      vaultBumps: [],
+      activeFlows: 0,
+      proposalCount: new BN(0),
     };
 
     return { safe, ctx };
@@ -103,7 +157,8 @@ export default class SafeInstructionService {
     program: Program<Snowflake>,
     safeSignerAddress: PublicKey,
     safeAddress: PublicKey,
-    safeOwner: PublicKey
+    safeOwner: PublicKey,
+    weight: number
   ): Promise<TransactionInstruction> {
     const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
       accounts: {
@@ -113,7 +168,7 @@ export default class SafeInstructionService {
       signers: [],
     };
 
-    const setOwnersIx = await program.instruction.addOwner(safeOwner, ctx);
+    const setOwnersIx = await program.instruction.addOwner(safeOwner, weight, ctx);
 
     return setOwnersIx;
   }
@@ -160,6 +215,430 @@ export default class SafeInstructionService {
     return ix;
   }
 
+  static async setOwnersIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    owners: PublicKey[],
+    ownerWeights: number[],
+    ownerRoles: number[],
+    approvalsRequired: number
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setOwners(
+      owners,
+      Buffer.from(ownerWeights),
+      Buffer.from(ownerRoles),
+      approvalsRequired,
+      ctx
+    );
+
+    return ix;
+  }
+
+  static async swapOwnerIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    oldOwner: PublicKey,
+    newOwner: PublicKey
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.swapOwner(oldOwner, newOwner, ctx);
+
+    return ix;
+  }
+
+  static async setOwnerRolesIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    safeOwner: PublicKey,
+    roles: number
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setOwnerRoles(safeOwner, roles, ctx);
+
+    return ix;
+  }
+
+  static async setConfigChangeDelayIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    delay: number
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setConfigChangeDelay(delay, ctx);
+
+    return ix;
+  }
+
+  static async setExtraIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    payerAddress: PublicKey,
+    extra: string
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner' | 'payer' | 'systemProgram'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+        payer: payerAddress,
+        systemProgram: SystemProgram.programId,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setExtra(extra, ctx);
+
+    return ix;
+  }
+
+  static async setMaxOwnersIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    payerAddress: PublicKey,
+    maxOwners: number
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner' | 'payer' | 'systemProgram'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+        payer: payerAddress,
+        systemProgram: SystemProgram.programId,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setMaxOwners(maxOwners, ctx);
+
+    return ix;
+  }
+
+  static async setGuardiansIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    guardians: PublicKey[],
+    guardianThreshold: number,
+    recoveryDelay: number
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setGuardians(
+      guardians,
+      guardianThreshold,
+      recoveryDelay,
+      ctx
+    );
+
+    return ix;
+  }
+
+  static async setPauserIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    pauser: PublicKey
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setPauser(pauser, ctx);
+
+    return ix;
+  }
+
+  static async addVaultIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.addVault(ctx);
+
+    return ix;
+  }
+
+  static async closeSafeIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    destinationAddress: PublicKey,
+    sweepSigner: boolean,
+    vaultAddresses: PublicKey[]
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<
+      'safe' | 'safeSigner' | 'flow' | 'destination' | 'systemProgram'
+    > = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+        flow: flowAddress,
+        destination: destinationAddress,
+        systemProgram: SystemProgram.programId,
+      },
+      remainingAccounts: vaultAddresses.map(vaultAddress => ({
+        pubkey: vaultAddress,
+        isSigner: false,
+        isWritable: true,
+      })),
+      signers: [],
+    };
+
+    const ix = await program.instruction.closeSafe(sweepSigner, ctx);
+
+    return ix;
+  }
+
+  static migrateSafeIxBase(safeAddress: PublicKey, payerAddress: PublicKey) {
+    const ctx: InstructionContextType<'safe' | 'payer' | 'systemProgram'> = {
+      accounts: {
+        safe: safeAddress,
+        payer: payerAddress,
+        systemProgram: SystemProgram.programId,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static migrateFlowIxBase(
+    flowAddress: PublicKey,
+    safeAddress: PublicKey,
+    payerAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'flow' | 'safe' | 'payer' | 'systemProgram'> = {
+      accounts: {
+        flow: flowAddress,
+        safe: safeAddress,
+        payer: payerAddress,
+        systemProgram: SystemProgram.programId,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static editActionIxBase(
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    requestedByAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy' | 'systemProgram'> = {
+      accounts: {
+        flow: flowAddress,
+        safe: safeAddress,
+        requestedBy: requestedByAddress,
+        systemProgram: SystemProgram.programId,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static rebaseFlowIxBase(
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    callerAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'safe' | 'flow' | 'caller' | 'systemProgram'> = {
+      accounts: {
+        safe: safeAddress,
+        flow: flowAddress,
+        caller: callerAddress,
+        systemProgram: SystemProgram.programId,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static pauseSafeIxBase(safeAddress: PublicKey, callerAddress: PublicKey) {
+    const ctx: InstructionContextType<'safe' | 'caller'> = {
+      accounts: {
+        safe: safeAddress,
+        caller: callerAddress,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static setDelegateIxBase(
+    safeAddress: PublicKey,
+    delegateRecordAddress: PublicKey,
+    ownerAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'safe' | 'delegateRecord' | 'owner' | 'systemProgram'> = {
+      accounts: {
+        safe: safeAddress,
+        delegateRecord: delegateRecordAddress,
+        owner: ownerAddress,
+        systemProgram: SystemProgram.programId,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static revokeDelegateIxBase(
+    safeAddress: PublicKey,
+    delegateRecordAddress: PublicKey,
+    ownerAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'safe' | 'delegateRecord' | 'owner'> = {
+      accounts: {
+        safe: safeAddress,
+        delegateRecord: delegateRecordAddress,
+        owner: ownerAddress,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static manageInstructionBufferIxBase(bufferAddress: PublicKey, creatorAddress: PublicKey) {
+    const ctx: InstructionContextType<'buffer' | 'creator'> = {
+      accounts: {
+        buffer: bufferAddress,
+        creator: creatorAddress,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static bufferedAction(ix: TransactionInstruction, bufferAddress: PublicKey) {
+    return {
+      ...SerializableAction.fromInstruction(ix),
+      instruction: HashService.sha256Buffer(ix.data),
+      instructionBuffer: bufferAddress,
+    };
+  }
+
+  static closeRecoveryIxBase(
+    safeAddress: PublicKey,
+    recoveryAddress: PublicKey,
+    initiatedByAddress: PublicKey,
+    callerAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'safe' | 'recovery' | 'initiatedBy' | 'caller'> = {
+      accounts: {
+        safe: safeAddress,
+        recovery: recoveryAddress,
+        initiatedBy: initiatedByAddress,
+        caller: callerAddress,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static async setAllowanceIx(
+    program: Program<Snowflake>,
+    safeSignerAddress: PublicKey,
+    safeAddress: PublicKey,
+    spender: PublicKey,
+    mint: PublicKey,
+    amount: BN,
+    period: number
+  ): Promise<TransactionInstruction> {
+    const ctx: InstructionContextType<'safe' | 'safeSigner'> = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+      },
+      signers: [],
+    };
+
+    const ix = await program.instruction.setAllowance(spender, mint, amount, period, ctx);
+
+    return ix;
+  }
+
+  static spendSolAllowanceIxBase(
+    safeAddress: PublicKey,
+    safeSignerAddress: PublicKey,
+    recipientAddress: PublicKey,
+    spenderAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<
+      'safe' | 'safeSigner' | 'recipient' | 'spender' | 'systemProgram'
+    > = {
+      accounts: {
+        safe: safeAddress,
+        safeSigner: safeSignerAddress,
+        recipient: recipientAddress,
+        spender: spenderAddress,
+        systemProgram: SystemProgram.programId,
+      },
+    };
+
+    return { ctx };
+  }
+
+  static manageConfigChangeIxBase(safeAddress: PublicKey, callerAddress: PublicKey) {
+    const ctx: InstructionContextType<'safe' | 'caller'> = {
+      accounts: {
+        safe: safeAddress,
+        caller: callerAddress,
+      },
+    };
+
+    return { ctx };
+  }
+
   static buildNewFlowJob(clientFlow: Flow, safeAddress: PublicKey) {
     const jobBuilder = new JobBuilder().jobName(clientFlow.name);
     if (clientFlow.recurring && clientFlow.triggerType === TriggerType.Time) {
@@ -172,7 +651,12 @@ export default class SafeInstructionService {
     job.triggerType = clientFlow.triggerType;
     const serializableJob = job.toSerializableJob();
     serializableJob.actions = clientFlow.actions;
-    serializableJob.ownerSetSeqno = clientFlow.ownerSetSeq;
+    serializableJob.ownerSetSeqno = new BN(clientFlow.ownerSetSeq ?? 0);
+    serializableJob.vaultIndex = clientFlow.vaultIndex ?? 0;
+    serializableJob.proposalIndex = new BN(0);
+    serializableJob.executedActions = 0;
+    serializableJob.approvalNonce = 0;
+    serializableJob.prerequisites = clientFlow.prerequisites ?? [];
     serializableJob.approvals = [];
     serializableJob.safe = safeAddress;
     serializableJob.proposalState = 0;
@@ -183,15 +667,14 @@ export default class SafeInstructionService {
   static async createFlowIx(
     program: Program,
     requestedByAddress: PublicKey,
-    account_size: number,
     clientFlow: Flow,
     safeAddress: PublicKey,
-    newFlowKeypair: Keypair,
+    flowAddress: PublicKey,
     systemProgram: PublicKey
   ): Promise<TransactionInstruction> {
     const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy' | 'systemProgram'> = {
       accounts: {
-        flow: newFlowKeypair.publicKey,
+        flow: flowAddress,
         safe: safeAddress,
         requestedBy: requestedByAddress,
         systemProgram,
@@ -200,21 +683,20 @@ export default class SafeInstructionService {
     };
 
     const serializableJob = this.buildNewFlowJob(clientFlow, safeAddress);
-    const createFlowIx = await program.instruction.createFlow(account_size, serializableJob, ctx);
+    const createFlowIx = await program.instruction.createFlow(serializableJob, ctx);
     return createFlowIx;
   }
 
   static createFlowIxBase(
     requestedByAddress: PublicKey,
-    account_size: number,
     clientFlow: Flow,
     safeAddress: PublicKey,
-    newFlowKeypair: Keypair,
+    flowAddress: PublicKey,
     systemProgram: PublicKey
   ) {
     const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy' | 'systemProgram'> = {
       accounts: {
-        flow: newFlowKeypair.publicKey,
+        flow: flowAddress,
         safe: safeAddress,
         requestedBy: requestedByAddress,
         systemProgram,
@@ -223,7 +705,7 @@ export default class SafeInstructionService {
     };
 
     const serializableJob = this.buildNewFlowJob(clientFlow, safeAddress);
-    return { accountSize: account_size, serializableJob, ctx };
+    return { serializableJob, ctx };
   }
 
   static abortFlowIxBase(flowAddress: PublicKey, safeAddress: PublicKey, callerAddress: PublicKey) {
@@ -242,11 +724,13 @@ export default class SafeInstructionService {
   static async deleteFlowIx(
     program: Program,
     ownerAddress: PublicKey,
-    flowAddress: PublicKey
+    flowAddress: PublicKey,
+    safeAddress: PublicKey
   ): Promise<TransactionInstruction> {
-    const ctx: InstructionContextType<'flow' | 'requestedBy'> = {
+    const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy'> = {
       accounts: {
         flow: flowAddress,
+        safe: safeAddress,
         requestedBy: ownerAddress,
       },
       signers: [],
@@ -256,10 +740,15 @@ export default class SafeInstructionService {
     return createFlowIx;
   }
 
-  static deleteFlowIxBase(ownerAddress: PublicKey, flowAddress: PublicKey) {
-    const ctx: InstructionContextType<'flow' | 'requestedBy'> = {
+  static deleteFlowIxBase(
+    ownerAddress: PublicKey,
+    flowAddress: PublicKey,
+    safeAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy'> = {
       accounts: {
         flow: flowAddress,
+        safe: safeAddress,
         requestedBy: ownerAddress,
       },
       signers: [],
@@ -268,12 +757,33 @@ export default class SafeInstructionService {
     return { ctx };
   }
 
+  static deleteExpiredFlowIxBase(
+    callerAddress: PublicKey,
+    requestedByAddress: PublicKey,
+    flowAddress: PublicKey,
+    safeAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy' | 'caller'> = {
+      accounts: {
+        flow: flowAddress,
+        safe: safeAddress,
+        requestedBy: requestedByAddress,
+        caller: callerAddress,
+      },
+      signers: [],
+    };
+
+    return { ctx };
+  }
+
   static async approveProposalIx(
     program: Program,
     safeAddress: PublicKey,
     flowAddress: PublicKey,
     payerAddress: PublicKey,
-    isApproved: boolean
+    isApproved: boolean,
+    contentHash: number[],
+    ownerAddress: PublicKey = payerAddress
   ) {
     const ctx: InstructionContextType<'safe' | 'flow' | 'caller'> = {
       accounts: {
@@ -284,7 +794,12 @@ export default class SafeInstructionService {
       signers: [],
     };
 
-    const approveProposalIx = await program.instruction.approveProposal(isApproved, ctx);
+    const approveProposalIx = await program.instruction.approveProposal(
+      isApproved,
+      contentHash,
+      ownerAddress,
+      ctx
+    );
     return approveProposalIx;
   }
 
@@ -292,7 +807,9 @@ export default class SafeInstructionService {
     safeAddress: PublicKey,
     flowAddress: PublicKey,
     payerAddress: PublicKey,
-    isApproved: boolean
+    isApproved: boolean,
+    contentHash: number[],
+    ownerAddress: PublicKey = payerAddress
   ) {
     const ctx: InstructionContextType<'safe' | 'flow' | 'caller'> = {
       accounts: {
@@ -303,7 +820,80 @@ export default class SafeInstructionService {
       signers: [],
     };
 
-    return { isApproved, ctx };
+    return { isApproved, contentHash, ownerAddress, ctx };
+  }
+
+  static revokeApprovalIxBase(
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    callerAddress: PublicKey
+  ) {
+    const ctx: InstructionContextType<'safe' | 'flow' | 'caller'> = {
+      accounts: {
+        safe: safeAddress,
+        flow: flowAddress,
+        caller: callerAddress,
+      },
+      signers: [],
+    };
+
+    return { ctx };
+  }
+
+  static flowContentHash(program: Program<Snowflake>, flow: any): number[] {
+    const int = (value: number | BN, bytes: number) =>
+      new BN(value).toTwos(bytes * 8).toArrayLike(Buffer, 'le', bytes);
+    const cron = Buffer.from(flow.cron);
+    const data = Buffer.concat([
+      int(flow.actions.length, 4),
+      ...flow.actions.map(action => program.coder.types.encode('Action', action)),
+      Buffer.from([flow.triggerType]),
+      int(flow.nextExecutionTime, 8),
+      int(flow.retryWindow, 4),
+      Buffer.from([flow.recurring ? 1 : 0]),
+      int(flow.remainingRuns, 2),
+      int(flow.scheduleEndDate, 8),
+      int(flow.userUtcOffset, 4),
+      Buffer.from([flow.vaultIndex]),
+      int(cron.length, 4),
+      cron,
+      int(flow.prerequisites.length, 4),
+      ...flow.prerequisites.map(prerequisite => prerequisite.toBuffer()),
+    ]);
+
+    return [...HashService.sha256Buffer(data)];
+  }
+
+  static approvalMessage(
+    flowAddress: PublicKey,
+    ownerSetSeqno: BN,
+    approvalNonce: number,
+    contentHash: number[],
+    isApproved: boolean
+  ) {
+    const nonce = Buffer.alloc(4);
+    nonce.writeUInt32LE(approvalNonce);
+    return Buffer.concat([
+      Buffer.from('snowflake-safe-approval'),
+      flowAddress.toBuffer(),
+      ownerSetSeqno.toArrayLike(Buffer, 'le', 8),
+      nonce,
+      Buffer.from(contentHash),
+      Buffer.from([isApproved ? 1 : 0]),
+    ]);
+  }
+
+  static approveProposalWithSignaturesIxBase(safeAddress: PublicKey, flowAddress: PublicKey) {
+    const ctx: InstructionContextType<'safe' | 'flow' | 'instructions'> = {
+      accounts: {
+        safe: safeAddress,
+        flow: flowAddress,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      },
+      signers: [],
+    };
+
+    return { ctx };
   }
 
   static executeMultisigFlowIxBase(
@@ -311,7 +901,8 @@ export default class SafeInstructionService {
     safeAddress: PublicKey,
     safeSignerAddress: PublicKey,
     ownerAddress: PublicKey,
-    flowActions: any
+    flowActions: any,
+    prerequisites: PublicKey[] = []
   ) {
     const remainingAccountMetas: AccountMeta[] = flowActions.reduce((result, current) => {
       const currentAccounts = current.accounts.map(account => {
@@ -322,9 +913,15 @@ export default class SafeInstructionService {
         isSigner: false,
         isWritable: false,
       });
+      if (current.instructionBuffer) {
+        result.push({ pubkey: current.instructionBuffer, isSigner: false, isWritable: false });
+      }
 
       return result;
     }, []);
+    prerequisites.forEach(prerequisite =>
+      remainingAccountMetas.push({ pubkey: prerequisite, isSigner: false, isWritable: false })
+    );
     const ctx: InstructionContextType<'flow' | 'safe' | 'safeSigner' | 'caller' | 'systemProgram'> = {
       accounts: {
         flow: flowAddress,
@@ -1,5 +1,6 @@
 import { Program, ProgramAccount, utils } from '@project-serum/anchor';
 import {
+  Ed25519Program,
   GetProgramAccountsFilter,
   Keypair,
   PublicKey,
@@ -21,11 +22,15 @@ export const FLOW_ACCOUNT_LAYOUT = BufferLayout.struct([
   BufferLayout.blob(32, 'safe'),
 ]);
 
-export const DEFAULT_FLOW_SIZE = 1800;
-
 export default class SafeService {
   constructor(readonly program: Program<Snowflake>) {}
-  async createSafe(payer: PublicKey, owners: PublicKey[], approvalsRequired: number) {
+  async createSafe(
+    payer: PublicKey,
+    owners: PublicKey[],
+    approvalsRequired: number,
+    ownerWeights: number[] = [],
+    ownerRoles: number[] = []
+  ) {
     const safeKeypair = Keypair.generate();
     const [safeSigner, safeSignerNonce] = await this.findSafeSignerAddress(safeKeypair.publicKey);
 
@@ -35,22 +40,52 @@ export default class SafeService {
       safeSigner,
       safeSignerNonce,
       owners,
-      approvalsRequired
+      approvalsRequired,
+      ownerWeights,
+      ownerRoles
     );
 
     return { safeKeypair, ...result };
   }
 
+  async createSafeWithSeed(
+    payer: PublicKey,
+    nonce: BN,
+    owners: PublicKey[],
+    approvalsRequired: number,
+    ownerWeights: number[] = [],
+    ownerRoles: number[] = []
+  ) {
+    const [safeAddress] = await this.findSafeAddress(payer, nonce);
+    const [safeSigner, safeSignerNonce] = await this.findSafeSignerAddress(safeAddress);
+
+    const result = SafeInstructionService.createSafeIxBase(
+      payer,
+      safeAddress,
+      safeSigner,
+      safeSignerNonce,
+      owners,
+      approvalsRequired,
+      ownerWeights,
+      ownerRoles
+    );
+
+    return { nonce, ...result };
+  }
+
   createAddAction(
+    safe: PublicKey,
     flow: PublicKey,
     requestedBy: PublicKey,
     ix: TransactionInstruction,
     finishDraft: boolean
   ) {
-    const ctx: InstructionContextType<'flow' | 'requestedBy'> = {
+    const ctx: InstructionContextType<'flow' | 'safe' | 'requestedBy' | 'systemProgram'> = {
       accounts: {
         flow: flow,
+        safe: safe,
         requestedBy: requestedBy,
+        systemProgram: programIds().system,
       },
       signers: [],
     };
@@ -60,9 +95,41 @@ export default class SafeService {
     return { ctx, builder };
   }
 
+  createReplaceAction(
+    safe: PublicKey,
+    flow: PublicKey,
+    requestedBy: PublicKey,
+    index: number,
+    ix: TransactionInstruction
+  ) {
+    const result = SafeInstructionService.editActionIxBase(safe, flow, requestedBy);
+    const builder = this.program.methods
+      .replaceAction(index, SerializableAction.fromInstruction(ix))
+      .accounts(result.ctx.accounts);
+    return { ...result, builder };
+  }
+
+  createRemoveAction(safe: PublicKey, flow: PublicKey, requestedBy: PublicKey, index: number) {
+    const result = SafeInstructionService.editActionIxBase(safe, flow, requestedBy);
+    const builder = this.program.methods.removeAction(index).accounts(result.ctx.accounts);
+    return { ...result, builder };
+  }
+
+  createReorderActions(
+    safe: PublicKey,
+    flow: PublicKey,
+    requestedBy: PublicKey,
+    order: number[]
+  ) {
+    const result = SafeInstructionService.editActionIxBase(safe, flow, requestedBy);
+    const builder = this.program.methods.reorderActions(order).accounts(result.ctx.accounts);
+    return { ...result, builder };
+  }
+
   async createAddOwnerInstruction(
     safeAddress: PublicKey,
-    safeOwner: PublicKey
+    safeOwner: PublicKey,
+    weight = 1
   ): Promise<TransactionInstruction[]> {
     const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
 
@@ -70,7 +137,8 @@ export default class SafeService {
       this.program,
       safeSignerAddress,
       safeAddress,
-      safeOwner
+      safeOwner,
+      weight
     );
 
     return [ix];
@@ -108,43 +176,564 @@ export default class SafeService {
     return [ix];
   }
 
-  createFlow(
-    requestedByAddress: PublicKey,
+  async createSetOwnersInstruction(
+    safeAddress: PublicKey,
+    owners: PublicKey[],
+    approvalsRequired: number,
+    ownerWeights: number[] = [],
+    ownerRoles: number[] = []
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setOwnersIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      owners,
+      ownerWeights,
+      ownerRoles,
+      approvalsRequired
+    );
+
+    return [ix];
+  }
+
+  async createSwapOwnerInstruction(
+    safeAddress: PublicKey,
+    oldOwner: PublicKey,
+    newOwner: PublicKey
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.swapOwnerIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      oldOwner,
+      newOwner
+    );
+
+    return [ix];
+  }
+
+  async createSetOwnerRolesInstruction(
+    safeAddress: PublicKey,
+    safeOwner: PublicKey,
+    roles: number
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setOwnerRolesIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      safeOwner,
+      roles
+    );
+
+    return [ix];
+  }
+
+  async createSetConfigChangeDelayInstruction(
+    safeAddress: PublicKey,
+    delay: number
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setConfigChangeDelayIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      delay
+    );
+
+    return [ix];
+  }
+
+  async createSetExtraInstruction(
+    safeAddress: PublicKey,
+    extra: string
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setExtraIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      safeSignerAddress,
+      extra
+    );
+
+    return [ix];
+  }
+
+  async createSetMaxOwnersInstruction(
+    safeAddress: PublicKey,
+    maxOwners: number
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setMaxOwnersIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      safeSignerAddress,
+      maxOwners
+    );
+
+    return [ix];
+  }
+
+  async createSetGuardiansInstruction(
+    safeAddress: PublicKey,
+    guardians: PublicKey[],
+    guardianThreshold: number,
+    recoveryDelay: number
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setGuardiansIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      guardians,
+      guardianThreshold,
+      recoveryDelay
+    );
+
+    return [ix];
+  }
+
+  async createSetPauserInstruction(
+    safeAddress: PublicKey,
+    pauser: PublicKey
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setPauserIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      pauser
+    );
+
+    return [ix];
+  }
+
+  async createAddVaultInstruction(safeAddress: PublicKey): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.addVaultIx(this.program, safeSignerAddress, safeAddress);
+
+    return [ix];
+  }
+
+  async createApproveProposalInstruction(
     safeAddress: PublicKey,
-    clientFlow: Flow,
-    newFlowKeypair: Keypair
+    flowAddress: PublicKey,
+    callerAddress: PublicKey,
+    isApproved: boolean
+  ): Promise<TransactionInstruction[]> {
+    const contentHash = await this.getFlowContentHash(flowAddress);
+    const ix = await SafeInstructionService.approveProposalIx(
+      this.program,
+      safeAddress,
+      flowAddress,
+      callerAddress,
+      isApproved,
+      contentHash
+    );
+
+    return [ix];
+  }
+
+  async createCloseSafeInstruction(
+    safeAddress: PublicKey,
+    destination: PublicKey,
+    sweepSigner: boolean,
+    flowAddress?: PublicKey
+  ): Promise<TransactionInstruction[]> {
+    const safe = await this.program.account.safe.fetch(safeAddress);
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+    const [nextFlowAddress] = await this.findFlowAddress(safeAddress, safe.proposalCount);
+    const vaultAddresses = await Promise.all(
+      (safe.vaultBumps as number[]).map(async (_, index) => {
+        const [vaultAddress] = await this.findVaultSignerAddress(safeAddress, index + 1);
+        return vaultAddress;
+      })
+    );
+
+    const ix = await SafeInstructionService.closeSafeIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      flowAddress ?? nextFlowAddress,
+      destination,
+      sweepSigner,
+      vaultAddresses
+    );
+
+    return [ix];
+  }
+
+  migrateSafe(walletKey: PublicKey, safeAddress: PublicKey) {
+    const result = SafeInstructionService.migrateSafeIxBase(safeAddress, walletKey);
+    const builder = this.program.methods.migrateSafe().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  migrateFlow(walletKey: PublicKey, safeAddress: PublicKey, flowAddress: PublicKey) {
+    const result = SafeInstructionService.migrateFlowIxBase(flowAddress, safeAddress, walletKey);
+    const builder = this.program.methods.migrateFlow().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  rebaseFlow(walletKey: PublicKey, safeAddress: PublicKey, flowAddress: PublicKey) {
+    const result = SafeInstructionService.rebaseFlowIxBase(safeAddress, flowAddress, walletKey);
+    const builder = this.program.methods.rebaseFlow().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  pauseSafe(walletKey: PublicKey, safeAddress: PublicKey) {
+    const result = SafeInstructionService.pauseSafeIxBase(safeAddress, walletKey);
+    const builder = this.program.methods.pauseSafe().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  unpauseSafe(walletKey: PublicKey, safeAddress: PublicKey) {
+    const result = SafeInstructionService.pauseSafeIxBase(safeAddress, walletKey);
+    const builder = this.program.methods.unpauseSafe().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  async setDelegate(
+    walletKey: PublicKey,
+    safeAddress: PublicKey,
+    delegate: PublicKey,
+    expiresAt = 0
+  ) {
+    const [delegateRecordAddress] = await this.findDelegateAddress(safeAddress, walletKey);
+    const result = SafeInstructionService.setDelegateIxBase(
+      safeAddress,
+      delegateRecordAddress,
+      walletKey
+    );
+    const builder = this.program.methods
+      .setDelegate(delegate, new BN(expiresAt))
+      .accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  async revokeDelegate(walletKey: PublicKey, safeAddress: PublicKey) {
+    const [delegateRecordAddress] = await this.findDelegateAddress(safeAddress, walletKey);
+    const result = SafeInstructionService.revokeDelegateIxBase(
+      safeAddress,
+      delegateRecordAddress,
+      walletKey
+    );
+    const builder = this.program.methods.revokeDelegate().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  createInstructionBuffer(creator: PublicKey, safeAddress: PublicKey, capacity: number) {
+    const buffer = Keypair.generate();
+    const builder = this.program.methods
+      .createInstructionBuffer(capacity)
+      .accounts({
+        buffer: buffer.publicKey,
+        safe: safeAddress,
+        creator,
+        systemProgram: programIds().system,
+      })
+      .signers([buffer]);
+
+    return { buffer, builder };
+  }
+
+  writeInstructionBuffer(
+    creator: PublicKey,
+    bufferAddress: PublicKey,
+    offset: number,
+    chunk: Buffer
+  ) {
+    const result = SafeInstructionService.manageInstructionBufferIxBase(bufferAddress, creator);
+    const builder = this.program.methods
+      .writeInstructionBuffer(offset, chunk)
+      .accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  sealInstructionBuffer(creator: PublicKey, bufferAddress: PublicKey) {
+    const result = SafeInstructionService.manageInstructionBufferIxBase(bufferAddress, creator);
+    const builder = this.program.methods.sealInstructionBuffer().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  closeInstructionBuffer(creator: PublicKey, bufferAddress: PublicKey) {
+    const result = SafeInstructionService.manageInstructionBufferIxBase(bufferAddress, creator);
+    const builder = this.program.methods.closeInstructionBuffer().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  async uploadInstructionBuffer(
+    creator: PublicKey,
+    safeAddress: PublicKey,
+    data: Buffer,
+    chunkSize = 800
+  ): Promise<PublicKey> {
+    const { buffer, builder } = this.createInstructionBuffer(creator, safeAddress, data.length);
+    await builder.rpc();
+    for (let offset = 0; offset < data.length; offset += chunkSize) {
+      const chunk = data.subarray(offset, offset + chunkSize);
+      await this.writeInstructionBuffer(creator, buffer.publicKey, offset, chunk).builder.rpc();
+    }
+    await this.sealInstructionBuffer(creator, buffer.publicKey).builder.rpc();
+
+    return buffer.publicKey;
+  }
+
+  async initiateRecovery(
+    guardian: PublicKey,
+    safeAddress: PublicKey,
+    newOwners: PublicKey[],
+    approvalsRequired: number
   ) {
+    const [recoveryAddress] = await this.findRecoveryAddress(safeAddress);
+    const builder = this.program.methods
+      .initiateRecovery(newOwners, approvalsRequired)
+      .accounts({ safe: safeAddress, recovery: recoveryAddress, guardian });
+
+    return { recoveryAddress, builder };
+  }
+
+  async approveRecovery(guardian: PublicKey, safeAddress: PublicKey) {
+    const [recoveryAddress] = await this.findRecoveryAddress(safeAddress);
+    const builder = this.program.methods
+      .approveRecovery()
+      .accounts({ safe: safeAddress, recovery: recoveryAddress, guardian });
+
+    return { recoveryAddress, builder };
+  }
+
+  async executeRecovery(caller: PublicKey, safeAddress: PublicKey, initiatedBy: PublicKey) {
+    const [recoveryAddress] = await this.findRecoveryAddress(safeAddress);
+    const result = SafeInstructionService.closeRecoveryIxBase(
+      safeAddress,
+      recoveryAddress,
+      initiatedBy,
+      caller
+    );
+    const builder = this.program.methods.executeRecovery().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  async cancelRecovery(caller: PublicKey, safeAddress: PublicKey, initiatedBy: PublicKey) {
+    const [recoveryAddress] = await this.findRecoveryAddress(safeAddress);
+    const result = SafeInstructionService.closeRecoveryIxBase(
+      safeAddress,
+      recoveryAddress,
+      initiatedBy,
+      caller
+    );
+    const builder = this.program.methods.cancelRecovery().accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  async createSetAllowanceInstruction(
+    safeAddress: PublicKey,
+    spender: PublicKey,
+    mint: PublicKey,
+    amount: BN,
+    period: number
+  ): Promise<TransactionInstruction[]> {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+
+    const ix = await SafeInstructionService.setAllowanceIx(
+      this.program,
+      safeSignerAddress,
+      safeAddress,
+      spender,
+      mint,
+      amount,
+      period
+    );
+
+    return [ix];
+  }
+
+  async spendSolAllowance(
+    spender: PublicKey,
+    safeAddress: PublicKey,
+    recipient: PublicKey,
+    amount: BN
+  ) {
+    const [safeSignerAddress] = await this.findSafeSignerAddress(safeAddress);
+    const result = SafeInstructionService.spendSolAllowanceIxBase(
+      safeAddress,
+      safeSignerAddress,
+      recipient,
+      spender
+    );
+    const builder = this.program.methods.spendSolAllowance(amount).accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  applyConfigChange(
+    walletKey: PublicKey,
+    safeAddress: PublicKey,
+    id: number,
+    payload: Buffer = Buffer.alloc(0)
+  ) {
+    const result = SafeInstructionService.manageConfigChangeIxBase(safeAddress, walletKey);
+    const builder = this.program.methods
+      .applyConfigChange(id, payload)
+      .accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  encodeConfigChangePayload(typeName: string, value: any): Buffer {
+    return this.program.coder.types.encode(typeName, value);
+  }
+
+  cancelConfigChange(walletKey: PublicKey, safeAddress: PublicKey, id: number) {
+    const result = SafeInstructionService.manageConfigChangeIxBase(safeAddress, walletKey);
+    const builder = this.program.methods.cancelConfigChange(id).accounts(result.ctx.accounts);
+
+    return { ...result, builder };
+  }
+
+  async createFlow(requestedByAddress: PublicKey, safeAddress: PublicKey, clientFlow: Flow) {
+    const safe = await this.program.account.safe.fetch(safeAddress);
+    const [flowAddress] = await this.findFlowAddress(safeAddress, safe.proposalCount);
     const data = SafeInstructionService.createFlowIxBase(
       requestedByAddress,
-      DEFAULT_FLOW_SIZE,
       clientFlow,
       safeAddress,
-      newFlowKeypair,
+      flowAddress,
       programIds().system
     );
-    return data;
+    return { ...data, flowAddress };
+  }
+
+  updateFlow(
+    requestedByAddress: PublicKey,
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    clientFlow: Flow
+  ) {
+    const result = SafeInstructionService.editActionIxBase(
+      safeAddress,
+      flowAddress,
+      requestedByAddress
+    );
+    const serializableJob = SafeInstructionService.buildNewFlowJob(clientFlow, safeAddress);
+    return { ...result, serializableJob };
   }
 
-  approveProposal(
+  async fetchFlows(safeAddress: PublicKey) {
+    const safe = await this.program.account.safe.fetch(safeAddress);
+    const flowAddresses = await Promise.all(
+      [...Array(safe.proposalCount.toNumber()).keys()].map(async index => {
+        const [flowAddress] = await this.findFlowAddress(safeAddress, new BN(index));
+        return flowAddress;
+      })
+    );
+    return this.program.account.flow.fetchMultiple(flowAddresses);
+  }
+
+  async getFlowContentHash(flowAddress: PublicKey): Promise<number[]> {
+    const flow = await this.program.account.flow.fetch(flowAddress);
+    return SafeInstructionService.flowContentHash(this.program, flow);
+  }
+
+  async approveProposal(
     walletKey: PublicKey,
     safeAddress: PublicKey,
     flowAddress: PublicKey,
-    isApproved: boolean
+    isApproved: boolean,
+    ownerAddress: PublicKey = walletKey
   ) {
+    const contentHash = await this.getFlowContentHash(flowAddress);
     const result = SafeInstructionService.approveProposalIxBase(
       safeAddress,
       flowAddress,
       walletKey,
-      isApproved
+      isApproved,
+      contentHash,
+      ownerAddress
     );
 
     const builder = this.program.methods
-      .approveProposal(result.isApproved)
-      .accounts(result.ctx.accounts);
+      .approveProposal(result.isApproved, result.contentHash, result.ownerAddress)
+      .accounts(result.ctx.accounts)
+      .remainingAccounts(await this.delegateRecordAccounts(safeAddress, ownerAddress, walletKey));
+
+    return { ...result, builder };
+  }
+
+  async revokeApproval(
+    walletKey: PublicKey,
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    ownerAddress: PublicKey = walletKey
+  ) {
+    const result = SafeInstructionService.revokeApprovalIxBase(
+      safeAddress,
+      flowAddress,
+      walletKey
+    );
+    const builder = this.program.methods
+      .revokeApproval()
+      .accounts(result.ctx.accounts)
+      .remainingAccounts(await this.delegateRecordAccounts(safeAddress, ownerAddress, walletKey));
 
     return { ...result, builder };
   }
 
+  async approveProposalWithSignatures(
+    safeAddress: PublicKey,
+    flowAddress: PublicKey,
+    approvers: Keypair[],
+    isApproved: boolean
+  ) {
+    const flow = await this.program.account.flow.fetch(flowAddress);
+    const message = SafeInstructionService.approvalMessage(
+      flowAddress,
+      flow.ownerSetSeqno,
+      flow.approvalNonce,
+      SafeInstructionService.flowContentHash(this.program, flow),
+      isApproved
+    );
+    const verifyIxs = approvers.map(approver =>
+      Ed25519Program.createInstructionWithPrivateKey({ privateKey: approver.secretKey, message })
+    );
+
+    const result = SafeInstructionService.approveProposalWithSignaturesIxBase(
+      safeAddress,
+      flowAddress
+    );
+    const builder = this.program.methods
+      .approveProposalWithSignatures()
+      .accounts(result.ctx.accounts)
+      .preInstructions(verifyIxs);
+
+    return { ...result, message, builder };
+  }
+
   private getSafeAddressFilter(publicKey: PublicKey): GetProgramAccountsFilter {
     return {
       memcmp: {
@@ -154,10 +743,70 @@ export default class SafeService {
     };
   }
 
+  async findSafeAddress(creator: PublicKey, nonce: BN): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddress(
+      [utils.bytes.utf8.encode('Safe'), creator.toBuffer(), nonce.toArrayLike(Buffer, 'le', 8)],
+      this.program.programId
+    );
+  }
+
+  async findRecoveryAddress(safeAddress: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddress(
+      [utils.bytes.utf8.encode('Recovery'), safeAddress.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  async findFlowAddress(safeAddress: PublicKey, proposalIndex: BN): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddress(
+      [
+        utils.bytes.utf8.encode('Flow'),
+        safeAddress.toBuffer(),
+        proposalIndex.toArrayLike(Buffer, 'le', 8),
+      ],
+      this.program.programId
+    );
+  }
+
+  async findDelegateAddress(
+    safeAddress: PublicKey,
+    ownerAddress: PublicKey
+  ): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddress(
+      [utils.bytes.utf8.encode('Delegate'), safeAddress.toBuffer(), ownerAddress.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  async delegateRecordAccounts(
+    safeAddress: PublicKey,
+    ownerAddress: PublicKey,
+    callerAddress: PublicKey
+  ) {
+    if (ownerAddress.equals(callerAddress)) {
+      return [];
+    }
+    const [delegateRecordAddress] = await this.findDelegateAddress(safeAddress, ownerAddress);
+    return [{ pubkey: delegateRecordAddress, isSigner: false, isWritable: false }];
+  }
+
   async findSafeSignerAddress(safeAddress: PublicKey): Promise<[PublicKey, number]> {
     return PublicKey.findProgramAddress(
       [utils.bytes.utf8.encode('SafeSigner'), safeAddress.toBuffer()],
       this.program.programId
     );
   }
+
+  async findVaultSignerAddress(
+    safeAddress: PublicKey,
+    vaultIndex: number
+  ): Promise<[PublicKey, number]> {
+    if (vaultIndex === 0) {
+      return this.findSafeSignerAddress(safeAddress);
+    }
+    return PublicKey.findProgramAddress(
+      [utils.bytes.utf8.encode('SafeSigner'), safeAddress.toBuffer(), Buffer.from([vaultIndex])],
+      this.program.programId
+    );
+  }
 }
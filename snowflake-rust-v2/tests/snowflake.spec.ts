@@ -1,4 +1,3 @@
-import { Keypair } from '@solana/web3.js';
 import { assert } from 'chai';
 import { JobBuilder, SerializableAction } from '@snowflake-so/snowflake-sdk';
 
@@ -12,6 +11,7 @@ import {
   ownerB,
   ownerC,
   ownerD,
+  EXECUTE_ALL_ACTIONS,
 } from './helper';
 
 describe('Snowflake', () => {
@@ -23,31 +23,28 @@ describe('Snowflake', () => {
     assert.strictEqual(safeAccount.signerBump, safe.signerBump);
     assert.strictEqual(safeAccount.approvalsRequired, 2);
     assert.deepEqual(safeAccount.owners, owners);
-    assert.strictEqual(safeAccount.ownerSetSeqno, 0);
+    assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 0);
 
     // Create add owner instruction
     const ixs = await safeService.createAddOwnerInstruction(ctx.accounts.safe, ownerD.publicKey);
     const [safeSignerAddress] = await safeService.findSafeSignerAddress(ctx.accounts.safe);
     const job = new JobBuilder().jobName('Add new owner').jobInstructions(ixs).build();
-    const newFlowKeypair = Keypair.generate();
     const {
-      accountSize,
       serializableJob,
       ctx: createFlowCtx,
-    } = safeService.createFlow(
+      flowAddress,
+    } = await safeService.createFlow(
       anchorProvider.wallet.publicKey,
       ctx.accounts.safe,
-      job.toSerializableJob(),
-      newFlowKeypair
+      job.toSerializableJob()
     );
 
     await program.methods
-      .createFlow(accountSize, serializableJob, false)
+      .createFlow(serializableJob, false)
       .accounts(createFlowCtx.accounts)
-      .signers([newFlowKeypair])
       .rpc();
 
-    let flowAccount = await program.account.flow.fetch(newFlowKeypair.publicKey);
+    let flowAccount = await program.account.flow.fetch(flowAddress);
 
     const action = (flowAccount.actions as any)[0];
     assert.strictEqual((flowAccount.actions as any).length, 1);
@@ -59,7 +56,7 @@ describe('Snowflake', () => {
     assert.deepEqual(action.instruction, SerializableAction.fromInstruction(ixs[0]).instruction);
 
     const executeData = SafeInstructionService.executeMultisigFlowIxBase(
-      newFlowKeypair.publicKey,
+      flowAddress,
       ctx.accounts.safe,
       safeSignerAddress,
       anchorProvider.wallet.publicKey,
@@ -67,15 +64,15 @@ describe('Snowflake', () => {
     );
 
     const executeBuilder = await program.methods
-      .executeMultisigFlow()
+      .executeMultisigFlow(EXECUTE_ALL_ACTIONS)
       .accounts(executeData.ctx.accounts)
       .remainingAccounts(executeData.ctx.remainingAccounts);
 
     // Approve transaction.
-    const approveData = safeService.approveProposal(
+    const approveData = await safeService.approveProposal(
       anchorProvider.wallet.publicKey,
       ctx.accounts.safe,
-      newFlowKeypair.publicKey,
+      flowAddress,
       true
     );
     await approveData.builder.rpc();
@@ -89,17 +86,17 @@ describe('Snowflake', () => {
     }
 
     // Other owner approves transaction.
-    const ownerBapproveData = safeService.approveProposal(
+    const ownerBapproveData = await safeService.approveProposal(
       ownerB.publicKey,
       ctx.accounts.safe,
-      newFlowKeypair.publicKey,
+      flowAddress,
       true
     );
     await ownerBapproveData.builder.signers([ownerB]).rpc();
 
     await executeBuilder.rpc();
 
-    flowAccount = await program.account.flow.fetch(newFlowKeypair.publicKey);
+    flowAccount = await program.account.flow.fetch(flowAddress);
     safeAccount = await program.account.safe.fetch(ctx.accounts.safe);
 
     assert.strictEqual((flowAccount.approvals as any).length, 2);
@@ -108,11 +105,11 @@ describe('Snowflake', () => {
     assert.ok(flowAccount.approvals[1].owner.equals(ownerB.publicKey));
     assert.strictEqual(flowAccount.approvals[1].isApproved, true);
     assert.strictEqual(flowAccount.proposalStage, ProposalStateType.Complete);
-    assert.strictEqual(flowAccount.ownerSetSeqno, 0);
+    assert.strictEqual(flowAccount.ownerSetSeqno.toNumber(), 0);
 
     assert.strictEqual(safeAccount.signerBump, safe.signerBump);
     assert.strictEqual(safeAccount.approvalsRequired, 2);
     assert.deepEqual(safeAccount.owners, [...owners, ownerD.publicKey]);
-    assert.strictEqual(safeAccount.ownerSetSeqno, 1);
+    assert.strictEqual(safeAccount.ownerSetSeqno.toNumber(), 1);
   });
 });